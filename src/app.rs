@@ -3,10 +3,9 @@ use std::string::String;
 use ratatui::backend::Backend;
 use ratatui::crossterm::event;
 use ratatui::crossterm::event::Event;
-use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::prelude::Line;
 use ratatui::Terminal;
-use ratatui::widgets::{Block, Borders, Paragraph};
+use crate::export::{self, PdfFont};
+use crate::export::gcode::GcodeUnits;
 use crate::shapes::*;
 use crate::ui::{get_instructions_for, ui, Instruction};
 
@@ -17,10 +16,15 @@ pub enum Pages {
     AddingBody,
     BodyView,
     RenamingBody,
+    SettingQuantity,
+    SettingKerf,
     ShowingHoleFeatureOptions,
     ShowingCornerFeatureOptions,
     ShowingCutoutFeatureOptions,
     ShowingCircularFeatureOptions,
+    ShowingExportOptions,
+    ImportingBody,
+    Verifying,
     AddingFeature,
     RemovingFeature,
     ResettingBody,
@@ -35,16 +39,37 @@ pub struct App {
     pub new_project_name: String,
     pub is_project_name_set: bool,
     pub body: Body,
+    /// The bodies that have already been finished in this project.
+    pub bodies: Vec<Body>,
     pub current_page: Pages,
     pub is_name_set: bool,
+    pub is_shape_set: bool,
     pub is_width_set: bool,
     pub is_height_set: bool,
     pub new_body_name: String,
+    /// The base shape picked for the new body.
+    pub new_body_shape: BodyShape,
+    /// The first dimension of the new body's shape, such as its width or diameter.
     pub new_body_width: String,
+    /// The second dimension of the new body's shape, such as its height or inner diameter.
     pub new_body_height: String,
+    pub new_body_quantity: String,
+    /// The kerf being typed for the body.
+    pub new_body_kerf: String,
     pub current_feature_addition_path: Option<FeatureAdditionPath>,
     pub feature_page_index: usize,
     pub feature_pages: Vec<Vec<String>>,
+    /// The font used for pdf exports.
+    pub pdf_font: PdfFont,
+    /// The units g-code exports are read in.
+    pub gcode_units: GcodeUnits,
+    /// A message about the last action, such as where an export was saved or why it failed.
+    pub message: String,
+    /// The path of the csv file being imported.
+    pub import_path: String,
+    /// The problems found in the last csv import.
+    pub import_errors: Vec<String>,
+    pub verification: Vec<String>,
 }
 
 impl App {
@@ -54,16 +79,27 @@ impl App {
             new_project_name: "".to_string(),
             is_project_name_set: false,
             body: Body::new(),
+            bodies: Vec::new(),
             current_page: Pages::Launching,
             is_name_set: false,
+            is_shape_set: false,
             is_width_set: false,
             is_height_set: false,
             new_body_name: "".to_string(),
+            new_body_shape: BodyShape::Rectangle,
             new_body_width: "".to_string(),
             new_body_height: "".to_string(),
+            new_body_quantity: "".to_string(),
+            new_body_kerf: "".to_string(),
             current_feature_addition_path: None,
             feature_page_index: 0,
             feature_pages: Vec::new(),
+            pdf_font: PdfFont::Sans,
+            gcode_units: GcodeUnits::Millimeters,
+            message: "".to_string(),
+            import_path: "".to_string(),
+            import_errors: Vec::new(),
+            verification: Vec::new(),
         }
     }
 
@@ -86,10 +122,7 @@ impl App {
             return Vec::new();
         }
 
-        self.feature_pages[self.feature_page_index]
-            .iter()
-            .map(|line| line.clone())
-            .collect()
+        self.feature_pages[self.feature_page_index].to_vec()
     }
     
     pub fn get_current_page_number(&self) -> String {
@@ -103,10 +136,15 @@ impl App {
             Pages::AddingBody => { "Adding Body".to_string() }
             Pages::BodyView => { format!("Body View {}", self.get_current_page_number()) }
             Pages::RenamingBody => { "Renaming Body".to_string() }
+            Pages::SettingQuantity => { "Setting Quantity".to_string() }
+            Pages::SettingKerf => { "Setting Kerf".to_string() }
             Pages::ShowingHoleFeatureOptions => { "Hole Feature Options".to_string() }
             Pages::ShowingCornerFeatureOptions => { "Corner Feature Options".to_string() }
             Pages::ShowingCutoutFeatureOptions => { "Cutout Feature Options".to_string() }
             Pages::ShowingCircularFeatureOptions => { "Circular Feature Options".to_string() }
+            Pages::ShowingExportOptions => { "Export Options".to_string() }
+            Pages::ImportingBody => { "Importing Body".to_string() }
+            Pages::Verifying => { format!("Verifying {}", self.get_current_page_number()) }
             Pages::AddingFeature => { "Adding Feature".to_string() }
             Pages::RemovingFeature => { format!("Removing Feature {}", self.get_current_page_number()) }
             Pages::ResettingBody => { "Resetting Body".to_string() }
@@ -115,13 +153,20 @@ impl App {
         }
     }
 
+    /// Returns the body's summary followed by a warning for every interference between its features.
+    fn body_summary(&self) -> Vec<String> {
+        let mut summary = self.body.summarize();
+        summary.extend(self.body.interferences().into_iter().map(|warning| format!("warning: {}", warning)));
+        summary
+    }
+
     pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         // running
         loop {
             // pre-render
             let footer_height = get_instructions_for(&self.current_page).len() as u16 + 2;
-            let header_height = 4;
-            let page_height = terminal.size()?.height - footer_height - header_height;
+            let header_height = 6;
+            let page_height = terminal.size()?.height.saturating_sub(footer_height + header_height);
 
 
 
@@ -132,9 +177,10 @@ impl App {
             let mut lines_used_in_current_page: u16 = 0;
             // the current page of features being assembled
             let mut current_page: Vec<String> = Vec::new();
-            current_page.extend(self.body.summarize());
+            let body_summary = self.body_summary();
+            current_page.extend(body_summary.clone());
             current_page.push("".to_string());
-            lines_used_in_current_page += self.body.summarize().len() as u16 + 1;
+            lines_used_in_current_page += body_summary.len() as u16 + 1;
             // iterating through the features
             for i in 0..self.body.features.len() {
                 // checks if a new page is needed
@@ -142,9 +188,9 @@ impl App {
                     new_feature_pages.push(current_page);
                     current_page = Vec::new();
                     lines_used_in_current_page = 0;
-                    current_page.extend(self.body.summarize());
+                    current_page.extend(body_summary.clone());
                     current_page.push("".to_string());
-                    lines_used_in_current_page += self.body.summarize().len() as u16 + 1;
+                    lines_used_in_current_page += body_summary.len() as u16 + 1;
                 }
                 // adds the feature to the current page
                 let lines_to_add = self.body.features[i].summarize();
@@ -154,17 +200,17 @@ impl App {
             }
             // adds the last page if it is not empty
             if !current_page.is_empty() { new_feature_pages.push(current_page); }
+            // the verification is paged instead while it is shown
+            if matches!(self.current_page, Pages::Verifying) {
+                new_feature_pages = self.verification.chunks(page_height.max(1) as usize).map(|page| page.to_vec()).collect();
+            }
 
             // updates the feature page count in the app
-            let cloned_new_feature_pages: Vec<Vec<String>> = new_feature_pages
-                .iter()
-                .map(|line| line.clone())
-                .collect();
             self.feature_pages = new_feature_pages;
 
             // makes sure that the feature page index is valid
-            if !self.feature_pages.is_empty() {
-                if self.feature_page_index >= self.feature_pages.len() { self.feature_page_index = self.feature_pages.len() - 1; }
+            if !self.feature_pages.is_empty() && self.feature_page_index >= self.feature_pages.len() {
+                self.feature_page_index = self.feature_pages.len() - 1;
             }
 
 
@@ -212,9 +258,11 @@ impl App {
                         // resets
                         if key.code == Instruction::reset_instruction().keybind {
                             self.new_body_name = "".to_string();
+                            self.new_body_shape = BodyShape::Rectangle;
                             self.new_body_width = "".to_string();
                             self.new_body_height = "".to_string();
                             self.is_name_set = false;
+                            self.is_shape_set = false;
                             self.is_width_set = false;
                             self.is_height_set = false;
                             continue;
@@ -225,37 +273,65 @@ impl App {
                             self.new_body_name = term_tools::keypad(&self.new_body_name, key);
                             if key.code == Instruction::confirm_instruction().keybind {
                                 if self.new_body_name.is_empty() { continue; }
+                                if self.new_body_name.contains(export::csv::RESERVED_CHARACTERS) {
+                                    self.message = "Body names cannot contain ; or =".to_string();
+                                    continue;
+                                }
                                 self.is_name_set = true;
                                 continue;
                             }
                         }
 
-                        // edits the new width before the height
-                        if !self.is_width_set && self.is_name_set {
+                        // picks the base shape after the name
+                        if !self.is_shape_set && self.is_name_set {
+                            let shape_instructions = Instruction::body_shape_instructions();
+                            if let Some(index) = shape_instructions.iter().position(|instruction| instruction.keybind == key.code) {
+                                self.new_body_shape = BodyShape::all()[index];
+                                self.is_shape_set = true;
+                            }
+                            continue;
+                        }
+
+                        // edits the first dimension before the second
+                        if !self.is_width_set && self.is_shape_set {
                             self.new_body_width = term_tools::numpad(&self.new_body_width, key);
                             if key.code == Instruction::confirm_instruction().keybind {
                                 if self.new_body_width.parse::<f64>().unwrap_or(0.0) <= 0.0 { continue; }
                                 self.is_width_set = true;
-                                continue;
+                                // shapes with a single dimension have no second one to enter
+                                if self.new_body_shape.dimension_names().len() == 1 {
+                                    self.new_body_height = self.new_body_width.clone();
+                                    self.is_height_set = true;
+                                }
+                                else { continue; }
                             }
                         }
 
-                        // edits the new height if the new width is set
-                        if !self.is_height_set && self.is_name_set && self.is_width_set {
+                        // edits the second dimension if the first is set
+                        if !self.is_height_set && self.is_shape_set && self.is_width_set {
                             self.new_body_height = term_tools::numpad(&self.new_body_height, key);
                             if key.code == Instruction::confirm_instruction().keybind {
-                                if self.new_body_height.parse::<f64>().unwrap_or(0.0) <= 0.0 { continue; }
+                                let first = self.new_body_width.parse::<f64>().unwrap_or(0.0);
+                                let second = self.new_body_height.parse::<f64>().unwrap_or(0.0);
+                                if let Err(error) = self.new_body_shape.check_dimensions(first, second) {
+                                    self.message = format!("Invalid {}: {}", self.new_body_shape.name(), error);
+                                    continue;
+                                }
                                 self.is_height_set = true;
                                 //continue;
                             }
                         }
 
-                        // creates a new body if both width and height are set
-                        if self.is_name_set && self.is_width_set && self.is_height_set {
+                        // creates a new body if every dimension is set
+                        if self.is_name_set && self.is_shape_set && self.is_width_set && self.is_height_set {
                             self.body = Body::new();
                             self.body.rename(self.new_body_name.clone());
-                            self.body.set_width(self.new_body_width.parse::<f64>().unwrap_or(0.0));
-                            self.body.set_height(self.new_body_height.parse::<f64>().unwrap_or(0.0));
+                            // the machine usually stays the same, so the last body's kerf carries over
+                            self.body.set_kerf(self.bodies.last().map_or(0.0, |body| body.kerf));
+                            let first = self.new_body_width.parse::<f64>().unwrap_or(0.0);
+                            let second = self.new_body_height.parse::<f64>().unwrap_or(0.0);
+                            self.body.set_shape_dimensions(self.new_body_shape, first, second);
+                            self.message = "".to_string();
                             self.current_page = Pages::BodyView;
                             continue;
                         }
@@ -310,6 +386,37 @@ impl App {
                             continue;
                         }
 
+                        // copies a text summary to the clipboard
+                        else if key.code == Instruction::copy_instruction().keybind {
+                            let summary = export::text::body_summary(&self.body, &self.project);
+                            match term_tools::copy_to_clipboard(&summary) {
+                                Ok(()) => { self.message = "Copied summary to the clipboard".to_string(); }
+                                Err(error) => { self.message = format!("Copy failed: {}", error); }
+                            }
+                            continue;
+                        }
+
+                        // checks the formulas against the drawn geometry
+                        else if key.code == Instruction::verify_instruction().keybind {
+                            self.verification = self.body.verify();
+                            self.feature_page_index = 0;
+                            self.current_page = Pages::Verifying;
+                            continue;
+                        }
+
+                        // shows export options
+                        else if key.code == Instruction::export_instruction().keybind {
+                            self.current_page = Pages::ShowingExportOptions;
+                            continue;
+                        }
+
+                        // imports a body
+                        else if key.code == Instruction::import_instruction().keybind {
+                            self.import_errors = Vec::new();
+                            self.current_page = Pages::ImportingBody;
+                            continue;
+                        }
+
                         // renames the project
                         else if key.code == Instruction::rename_project_instruction().keybind {
                             self.new_project_name = self.project.clone();
@@ -324,6 +431,20 @@ impl App {
                             continue;
                         }
 
+                        // sets the quantity
+                        else if key.code == Instruction::set_quantity_instruction().keybind {
+                            self.new_body_quantity = self.body.quantity.to_string();
+                            self.current_page = Pages::SettingQuantity;
+                            continue;
+                        }
+
+                        // sets the kerf
+                        else if key.code == Instruction::set_kerf_instruction().keybind {
+                            self.new_body_kerf = self.body.kerf.to_string();
+                            self.current_page = Pages::SettingKerf;
+                            continue;
+                        }
+
                         // removes a feature
                         else if key.code == Instruction::remove_feature_instruction().keybind {
                             self.current_page = Pages::RemovingFeature;
@@ -357,12 +478,54 @@ impl App {
                         // renames the body
                         if key.code == Instruction::confirm_instruction().keybind {
                             if self.new_body_name.is_empty() { continue; }
+                            if self.new_body_name.contains(export::csv::RESERVED_CHARACTERS) {
+                                self.message = "Body names cannot contain ; or =".to_string();
+                                continue;
+                            }
                             self.is_name_set = true;
                             self.body.rename(self.new_body_name.clone());
                             self.current_page = Pages::BodyView;
                         }
                     }
 
+                    Pages::SettingQuantity => {
+                        // resets
+                        if key.code == Instruction::reset_instruction().keybind {
+                            self.new_body_quantity = "".to_string();
+                            continue;
+                        }
+
+                        // edits the new quantity
+                        self.new_body_quantity = term_tools::numpad(&self.new_body_quantity, key);
+
+                        // sets the quantity
+                        if key.code == Instruction::confirm_instruction().keybind {
+                            let quantity = self.new_body_quantity.parse::<usize>().unwrap_or(0);
+                            if quantity == 0 { continue; }
+                            self.body.set_quantity(quantity);
+                            self.current_page = Pages::BodyView;
+                        }
+                    }
+
+                    Pages::SettingKerf => {
+                        // resets
+                        if key.code == Instruction::reset_instruction().keybind {
+                            self.new_body_kerf = "".to_string();
+                            continue;
+                        }
+
+                        // edits the new kerf
+                        self.new_body_kerf = term_tools::numpad(&self.new_body_kerf, key);
+
+                        // sets the kerf, which may be zero for no compensation
+                        if key.code == Instruction::confirm_instruction().keybind {
+                            let Ok(kerf) = self.new_body_kerf.parse::<f64>() else { continue; };
+                            if kerf < 0.0 { continue; }
+                            self.body.set_kerf(kerf);
+                            self.current_page = Pages::BodyView;
+                        }
+                    }
+
                     Pages::ShowingHoleFeatureOptions => {
                         // cancels
                         if key.code == Instruction::cancel_instruction().keybind {
@@ -387,6 +550,48 @@ impl App {
                             self.start_adding_feature(Features::RectangularHoleFeature);
                             continue;
                         }
+
+                        // adds a polygon hole
+                        else if key.code == Instruction::add_polygon_hole_instruction().keybind {
+                            self.start_adding_feature(Features::PolygonHoleFeature);
+                            continue;
+                        }
+
+                        // adds a keyhole
+                        else if key.code == Instruction::add_keyhole_instruction().keybind {
+                            self.start_adding_feature(Features::KeyholeFeature);
+                            continue;
+                        }
+
+                        // adds a d hole
+                        else if key.code == Instruction::add_d_hole_instruction().keybind {
+                            self.start_adding_feature(Features::DHoleFeature);
+                            continue;
+                        }
+
+                        // adds a polar hole pattern
+                        else if key.code == Instruction::add_polar_hole_pattern_instruction().keybind {
+                            self.start_adding_feature(Features::PolarHolePatternFeature);
+                            continue;
+                        }
+
+                        // adds a grid hole pattern
+                        else if key.code == Instruction::add_grid_hole_pattern_instruction().keybind {
+                            self.start_adding_feature(Features::GridHolePatternFeature);
+                            continue;
+                        }
+
+                        // adds an elliptical hole
+                        else if key.code == Instruction::add_elliptical_hole_instruction().keybind {
+                            self.start_adding_feature(Features::EllipticalHoleFeature);
+                            continue;
+                        }
+
+                        // adds a segment hole
+                        else if key.code == Instruction::add_segment_hole_instruction().keybind {
+                            self.start_adding_feature(Features::SegmentHoleFeature);
+                            continue;
+                        }
                     }
 
                     Pages::ShowingCornerFeatureOptions => {
@@ -408,6 +613,12 @@ impl App {
                             continue;
                         }
 
+                        // adds an internal corner
+                        else if key.code == Instruction::add_internal_corner_instruction().keybind {
+                            self.start_adding_feature(Features::InternalCornerFeature);
+                            continue;
+                        }
+
                         // adds a slope
                         else if key.code == Instruction::add_slope_instruction().keybind {
                             self.start_adding_feature(Features::SlopeFeature);
@@ -451,6 +662,36 @@ impl App {
                             self.start_adding_feature(Features::CompositeSlopeFeature);
                             continue;
                         }
+
+                        // adds an edge slot
+                        else if key.code == Instruction::add_edge_slot_instruction().keybind {
+                            self.start_adding_feature(Features::EdgeSlotFeature);
+                            continue;
+                        }
+
+                        // adds a dovetail
+                        else if key.code == Instruction::add_dovetail_instruction().keybind {
+                            self.start_adding_feature(Features::DovetailFeature);
+                            continue;
+                        }
+
+                        // adds a t-slot
+                        else if key.code == Instruction::add_t_slot_instruction().keybind {
+                            self.start_adding_feature(Features::TSlotFeature);
+                            continue;
+                        }
+
+                        // adds a free-form edge
+                        else if key.code == Instruction::add_free_form_edge_instruction().keybind {
+                            self.start_adding_feature(Features::FreeFormEdgeFeature);
+                            continue;
+                        }
+
+                        // adds a periodic edge
+                        else if key.code == Instruction::add_periodic_edge_instruction().keybind {
+                            self.start_adding_feature(Features::PeriodicEdgeFeature);
+                            continue;
+                        }
                     }
 
                     Pages::ShowingCircularFeatureOptions => {
@@ -471,6 +712,163 @@ impl App {
                             self.start_adding_feature(Features::EllipseFeature);
                             continue;
                         }
+
+                        // adds a gear
+                        else if key.code == Instruction::add_gear_instruction().keybind {
+                            self.start_adding_feature(Features::GearFeature);
+                            continue;
+                        }
+
+                        // adds an elliptical arc
+                        else if key.code == Instruction::add_elliptical_arc_instruction().keybind {
+                            self.start_adding_feature(Features::EllipticalArcFeature);
+                            continue;
+                        }
+                    }
+
+                    Pages::ShowingExportOptions => {
+                        // cancels
+                        if key.code == Instruction::cancel_instruction().keybind {
+                            self.current_page = Pages::BodyView;
+                            continue;
+                        }
+
+                        // changes the pdf font
+                        else if key.code == Instruction::change_font_instruction().keybind {
+                            self.pdf_font = self.pdf_font.next();
+                            continue;
+                        }
+
+                        // changes the g-code units
+                        else if key.code == Instruction::change_gcode_units_instruction().keybind {
+                            self.gcode_units = self.gcode_units.next();
+                            continue;
+                        }
+
+                        // exports a pdf
+                        else if key.code == Instruction::export_pdf_instruction().keybind {
+                            let result = export::pdf::print_body_summary(&self.body, &self.project, self.pdf_font);
+                            self.finish_export(result);
+                            continue;
+                        }
+
+                        // exports a csv
+                        else if key.code == Instruction::export_csv_instruction().keybind {
+                            let result = export::csv::print_body(&self.body, &self.project);
+                            self.finish_export(result);
+                            continue;
+                        }
+
+                        // exports a markdown report
+                        else if key.code == Instruction::export_markdown_instruction().keybind {
+                            let result = export::markdown::print_body_report(&self.body, &self.project);
+                            self.finish_export(result);
+                            continue;
+                        }
+
+                        // exports an html report
+                        else if key.code == Instruction::export_html_instruction().keybind {
+                            let result = export::html::print_body_report(&self.body, &self.project);
+                            self.finish_export(result);
+                            continue;
+                        }
+
+                        // exports a text summary
+                        else if key.code == Instruction::export_text_instruction().keybind {
+                            let result = export::text::print_body_summary(&self.body, &self.project);
+                            self.finish_export(result);
+                            continue;
+                        }
+
+                        // exports a dxf drawing
+                        else if key.code == Instruction::export_dxf_instruction().keybind {
+                            let result = export::dxf::print_drawing(&self.body, &self.project);
+                            self.finish_export(result);
+                            continue;
+                        }
+
+                        // exports an svg drawing
+                        else if key.code == Instruction::export_svg_instruction().keybind {
+                            let result = export::svg::print_drawing(&self.body, &self.project);
+                            self.finish_export(result);
+                            continue;
+                        }
+
+                        // exports a g-code program
+                        else if key.code == Instruction::export_gcode_instruction().keybind {
+                            let result = export::gcode::print_program(&self.body, &self.project, self.gcode_units);
+                            self.finish_export(result);
+                            continue;
+                        }
+
+                        // exports a project report with the finished bodies and the current body
+                        else if key.code == Instruction::export_project_report_instruction().keybind {
+                            let mut bodies: Vec<&Body> = self.bodies.iter().collect();
+                            if !self.body.is_blank() { bodies.push(&self.body); }
+                            let result = export::pdf::print_project_report(&bodies, &self.project, self.pdf_font);
+                            self.finish_export(result);
+                            continue;
+                        }
+                    }
+
+                    Pages::Verifying => {
+                        // returns to the body
+                        if key.code == Instruction::cancel_instruction().keybind {
+                            self.feature_page_index = 0;
+                            self.current_page = Pages::BodyView;
+                            continue;
+                        }
+
+                        // goes to the previous page
+                        else if key.code == Instruction::previous_page().keybind {
+                            self.go_to_previous_feature_page();
+                            continue;
+                        }
+
+                        // goes to the next page
+                        else if key.code == Instruction::next_page().keybind {
+                            self.go_to_next_feature_page();
+                            continue;
+                        }
+                    }
+
+                    Pages::ImportingBody => {
+                        // cancels
+                        if key.code == Instruction::escape_cancel_instruction().keybind {
+                            self.current_page = Pages::BodyView;
+                            continue;
+                        }
+
+                        // edits the import path
+                        self.import_path = term_tools::keypad(&self.import_path, key);
+
+                        // imports the body
+                        if key.code == Instruction::confirm_instruction().keybind {
+                            if self.import_path.is_empty() { continue; }
+                            match export::csv::import_body(&self.import_path) {
+                                Ok(body) => {
+                                    // keeps an unfinished body with the project instead of losing it
+                                    self.message = format!("Imported {}", self.import_path.trim());
+                                    if !self.body.is_blank() {
+                                        self.message += &format!(", kept {} with the project", self.body.name);
+                                        self.bodies.push(std::mem::replace(&mut self.body, Body::new()));
+                                    }
+                                    self.new_body_name = body.name.clone();
+                                    self.new_body_shape = body.shape;
+                                    self.new_body_width = body.width.to_string();
+                                    self.new_body_height = body.height.to_string();
+                                    self.is_name_set = true;
+                                    self.is_shape_set = true;
+                                    self.is_width_set = true;
+                                    self.is_height_set = true;
+                                    self.body = body;
+                                    self.import_errors = Vec::new();
+                                    self.current_page = Pages::BodyView;
+                                }
+                                Err(errors) => { self.import_errors = errors; }
+                            }
+                            continue;
+                        }
                     }
 
                     Pages::AddingFeature => {
@@ -482,12 +880,20 @@ impl App {
 
                         // updates the current value input and finishes the current step if the confirmation key is pressed
                         if let Some(path) = &mut self.current_feature_addition_path {
-                            let new_value_input = term_tools::numpad(&path.current_step_value_input(), key);
+                            let new_value_input = match path.current_step_kind() {
+                                StepKind::Number => { term_tools::numpad(&path.current_step_value_input(), key) }
+                                StepKind::Signed => { term_tools::signed_numpad(&path.current_step_value_input(), key) }
+                                StepKind::Text => { term_tools::keypad(&path.current_step_value_input(), key) }
+                            };
                             path.update_current_step_value_input(new_value_input);
 
                             if key.code == Instruction::confirm_instruction().keybind {
                                 let result = path.finish_current_step();
                                 if let Some(feature) = result {
+                                    if let Err(error) = self.body.check_fit(feature.as_ref()) {
+                                        path.reject(error);
+                                        continue;
+                                    }
                                     self.body.add(feature);
                                     self.current_feature_addition_path = None;
                                     self.current_page = Pages::BodyView;
@@ -522,9 +928,11 @@ impl App {
                         // resets the body
                         else if key.code == Instruction::confirm_instruction().keybind {
                             self.new_body_name = "".to_string();
+                            self.new_body_shape = BodyShape::Rectangle;
                             self.new_body_width = "".to_string();
                             self.new_body_height = "".to_string();
                             self.is_name_set = false;
+                            self.is_shape_set = false;
                             self.is_width_set = false;
                             self.is_height_set = false;
                             self.body = Body::new();
@@ -540,16 +948,40 @@ impl App {
                             continue;
                         }
 
-                        // finishes the body
-                        else if key.code == Instruction::confirm_instruction().keybind {
-                            self.body.print_pdf_summary(self.project.clone());
+                        // changes the pdf font
+                        else if key.code == Instruction::change_font_instruction().keybind {
+                            self.pdf_font = self.pdf_font.next();
+                            continue;
+                        }
+
+                        // finishes the body, and exports the project report of every finished body if asked
+                        else if key.code == Instruction::confirm_instruction().keybind || key.code == Instruction::finish_with_report_instruction().keybind {
+                            // keeps the body if the export fails so that no work is lost
+                            match export::pdf::print_body_summary(&self.body, &self.project, self.pdf_font) {
+                                Ok(path) => { self.message = format!("Saved {}", path); }
+                                Err(error) => {
+                                    self.message = format!("Export failed: {}", error);
+                                    self.current_page = Pages::BodyView;
+                                    continue;
+                                }
+                            }
                             self.new_body_name = "".to_string();
+                            self.new_body_shape = BodyShape::Rectangle;
                             self.new_body_width = "".to_string();
                             self.new_body_height = "".to_string();
                             self.is_name_set = false;
+                            self.is_shape_set = false;
                             self.is_width_set = false;
                             self.is_height_set = false;
-                            self.body = Body::new();
+                            // keeps the finished body for the project report
+                            self.bodies.push(std::mem::replace(&mut self.body, Body::new()));
+                            if key.code == Instruction::finish_with_report_instruction().keybind {
+                                let bodies: Vec<&Body> = self.bodies.iter().collect();
+                                match export::pdf::print_project_report(&bodies, &self.project, self.pdf_font) {
+                                    Ok(path) => { self.message += &format!(", {}", path); }
+                                    Err(error) => { self.message = format!("Project report failed: {}", error); }
+                                }
+                            }
                             self.current_page = Pages::AddingBody;
                             continue;
                         }
@@ -573,6 +1005,15 @@ impl App {
         Ok(())
     }
 
+    /// Records where an export was saved, or why it failed, and returns to the body view.
+    pub fn finish_export(&mut self, result: std::result::Result<String, Box<dyn std::error::Error>>) {
+        match result {
+            Ok(path) => { self.message = format!("Saved {}", path); }
+            Err(error) => { self.message = format!("Export failed: {}", error); }
+        }
+        self.current_page = Pages::BodyView;
+    }
+
     pub fn start_adding_feature(&mut self, feature: Features) {
         self.current_feature_addition_path = Some(feature.path());
         self.current_page = Pages::AddingFeature;
@@ -582,9 +1023,40 @@ impl App {
 
 
 pub mod term_tools {
+    use std::io::Write;
     use ratatui::crossterm::event;
     use ratatui::crossterm::event::{KeyCode, KeyEvent};
 
+    /// The characters used for base64 encoding.
+    const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    /// Copies text to the system clipboard with the OSC 52 terminal escape sequence.
+    /// This works over ssh and in most modern terminals without any clipboard libraries.
+    pub fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+        let mut stdout = std::io::stdout();
+        write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+        stdout.flush()
+    }
+
+    /// Encodes bytes as padded base64.
+    fn base64(bytes: &[u8]) -> String {
+        let mut encoded = String::new();
+        for chunk in bytes.chunks(3) {
+            let group = (chunk[0] as u32) << 16
+                | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+                | *chunk.get(2).unwrap_or(&0) as u32;
+            for index in 0..4 {
+                if index <= chunk.len() {
+                    encoded.push(BASE64_ALPHABET[(group >> (18 - index * 6) & 0x3f) as usize] as char);
+                }
+                else {
+                    encoded.push('=');
+                }
+            }
+        }
+        encoded
+    }
+
     pub fn numpad(field: &str, input: KeyEvent) -> String {
         if input.kind == event::KeyEventKind::Release { return field.to_string(); }
 
@@ -597,7 +1069,7 @@ pub mod term_tools {
             KeyCode::Char(char) => {
                 match char {
                     '0'..='9' => field.push(char),
-                    '.' => { if !field.contains('.') { field.push(char); } }
+                    '.' if !field.contains('.') => field.push(char),
                     _ => {}
                 }
             }
@@ -607,8 +1079,16 @@ pub mod term_tools {
         field
     }
 
+    pub fn signed_numpad(field: &str, input: KeyEvent) -> String {
+        if input.kind != event::KeyEventKind::Release && input.code == KeyCode::Char('-') && field.is_empty() {
+            return "-".to_string();
+        }
+
+        numpad(field, input)
+    }
+
     pub fn keypad(field: &str, input: KeyEvent) -> String {
-        if input.kind == event::KeyEventKind::Release {}
+        if input.kind == event::KeyEventKind::Release { return field.to_string(); }
 
         let mut field = field.to_string();
         match input.code {
@@ -0,0 +1,1196 @@
+use crate::shapes::Body;
+
+
+
+/// The font families that can be used when exporting a pdf.
+/// Every family is bundled into the binary so exporting works no matter where Peri is launched from.
+#[derive(Copy, Clone, PartialEq)]
+pub enum PdfFont {
+    Sans,
+    Serif,
+    Mono,
+}
+impl PdfFont {
+    /// Returns the display name of the font.
+    pub fn name(&self) -> String {
+        match self {
+            PdfFont::Sans => { "Liberation Sans".to_string() }
+            PdfFont::Serif => { "Liberation Serif".to_string() }
+            PdfFont::Mono => { "Liberation Mono".to_string() }
+        }
+    }
+
+    /// Returns the next font in the list, wrapping around to the first.
+    pub fn next(&self) -> PdfFont {
+        match self {
+            PdfFont::Sans => { PdfFont::Serif }
+            PdfFont::Serif => { PdfFont::Mono }
+            PdfFont::Mono => { PdfFont::Sans }
+        }
+    }
+
+    /// Returns the bundled regular, bold, italic and bold italic font files.
+    fn files(&self) -> [&'static [u8]; 4] {
+        match self {
+            PdfFont::Sans => {[
+                include_bytes!("../fonts/LiberationSans-Regular.ttf"),
+                include_bytes!("../fonts/LiberationSans-Bold.ttf"),
+                include_bytes!("../fonts/LiberationSans-Italic.ttf"),
+                include_bytes!("../fonts/LiberationSans-BoldItalic.ttf"),
+            ]}
+            PdfFont::Serif => {[
+                include_bytes!("../fonts/LiberationSerif-Regular.ttf"),
+                include_bytes!("../fonts/LiberationSerif-Bold.ttf"),
+                include_bytes!("../fonts/LiberationSerif-Italic.ttf"),
+                include_bytes!("../fonts/LiberationSerif-BoldItalic.ttf"),
+            ]}
+            PdfFont::Mono => {[
+                include_bytes!("../fonts/LiberationMono-Regular.ttf"),
+                include_bytes!("../fonts/LiberationMono-Bold.ttf"),
+                include_bytes!("../fonts/LiberationMono-Italic.ttf"),
+                include_bytes!("../fonts/LiberationMono-BoldItalic.ttf"),
+            ]}
+        }
+    }
+
+    /// Loads the font family from the bundled font files.
+    pub fn family(&self) -> Result<genpdf::fonts::FontFamily<genpdf::fonts::FontData>, genpdf::error::Error> {
+        let [regular, bold, italic, bold_italic] = self.files();
+        Ok(genpdf::fonts::FontFamily {
+            regular: genpdf::fonts::FontData::new(regular.to_vec(), None)?,
+            bold: genpdf::fonts::FontData::new(bold.to_vec(), None)?,
+            italic: genpdf::fonts::FontData::new(italic.to_vec(), None)?,
+            bold_italic: genpdf::fonts::FontData::new(bold_italic.to_vec(), None)?,
+        })
+    }
+}
+
+
+
+/// Returns the file name used for every export of a body, without an extension.
+pub fn file_stem(project: &str, body: &Body) -> String {
+    format!("{} - {}", project, &body.name)
+}
+
+
+
+/// Returns today's date (UTC) as year-month-day.
+pub fn today() -> String {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    // converts days since 1970-01-01 to a civil date
+    let days = (seconds / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+
+
+/// Returns the body's summary lines without their trailing separators.
+fn summary_lines(body: &Body) -> Vec<String> {
+    body.summarize()
+        .iter()
+        .map(|line| line.trim_end_matches(", ").to_string())
+        .collect()
+}
+
+
+
+/// Returns a row for each feature with its name, count, dimensions and modification.
+fn feature_table(body: &Body) -> Vec<[String; 4]> {
+    body.features
+        .iter()
+        .map(|feature| [
+            feature.shape().name(),
+            feature.count().to_string(),
+            feature.summarize_dimensions().concat().trim_end_matches(", ").to_string(),
+            format!("{:.3}", feature.value()),
+        ])
+        .collect()
+}
+
+
+
+/// Pdf exports.
+pub mod pdf {
+    use genpdf::{Alignment, Context, Element, PageDecorator, Position, SimplePageDecorator};
+    use genpdf::elements::{Break, FrameCellDecorator, PageBreak, Paragraph as PdfParagraph, TableLayout};
+    use genpdf::render::Area;
+    use genpdf::style::Style;
+    use crate::export::{file_stem, summary_lines, today, PdfFont};
+    use crate::shapes::Body;
+
+    /// Prints a pdf summary of the body and returns the path of the new file.
+    pub fn print_body_summary(body: &Body, project: &str, font: PdfFont) -> Result<String, Box<dyn std::error::Error>> {
+        // pdf
+        let mut pdf = genpdf::Document::new(font.family()?);
+
+        // title
+        let title = format!("{} - {}", project, &body.name);
+        pdf.set_title(&body.name);
+
+        // margins
+        let mut decorator = SimplePageDecorator::new();
+        decorator.set_margins(10);
+        pdf.set_page_decorator(decorator);
+
+        // building the page
+        let mut details = Vec::new();
+        details.push(title);
+        details.push("".to_string());
+        details.append(&mut body.summarize());
+        details.push("".to_string());
+        for feature in &body.features {
+            details.push("".to_string());
+            let feature_summary = feature.summarize();
+            details.extend(feature_summary);
+        }
+
+        for detail in details { pdf.push(PdfParagraph::new(detail)); }
+
+        // saving
+        let path = format!("./{}.pdf", file_stem(project, body));
+        pdf.render_to_file(&path)?;
+        Ok(path)
+    }
+
+    /// Prints a single pdf report for every body in the project and returns the path of the new file.
+    /// The report has a cover page, a summary table of the bodies and then a detailed section for each body.
+    pub fn print_project_report(bodies: &[&Body], project: &str, font: PdfFont) -> Result<String, Box<dyn std::error::Error>> {
+        // pdf
+        let mut pdf = genpdf::Document::new(font.family()?);
+        pdf.set_title(project);
+        pdf.set_page_decorator(ReportPageDecorator::new(project.to_string()));
+
+        // cover page
+        let total_parts: usize = bodies.iter().map(|body| body.quantity).sum();
+        pdf.push(Break::new(8));
+        pdf.push(PdfParagraph::new(project).aligned(Alignment::Center).styled(Style::new().bold().with_font_size(24)));
+        pdf.push(Break::new(2));
+        pdf.push(PdfParagraph::new(format!("Date: {}", today())).aligned(Alignment::Center));
+        pdf.push(PdfParagraph::new(format!("Bodies: {}", bodies.len())).aligned(Alignment::Center));
+        pdf.push(PdfParagraph::new(format!("Total parts: {}", total_parts)).aligned(Alignment::Center));
+        pdf.push(PageBreak::new());
+
+        // summary table
+        pdf.push(PdfParagraph::new("Summary").styled(Style::new().bold().with_font_size(16)));
+        pdf.push(Break::new(1));
+        let mut table = TableLayout::new(vec![3, 2, 1, 2, 2, 1]);
+        table.set_cell_decorator(FrameCellDecorator::new(true, true, false));
+        let heading = Style::new().bold();
+        table.row()
+            .element(PdfParagraph::new("Body").styled(heading))
+            .element(PdfParagraph::new("Perimeter").styled(heading))
+            .element(PdfParagraph::new("Qty").styled(heading))
+            .element(PdfParagraph::new("Extended").styled(heading))
+            .element(PdfParagraph::new("Toolpath").styled(heading))
+            .element(PdfParagraph::new("Pierces").styled(heading))
+            .push()?;
+        for body in bodies {
+            table.row()
+                .element(PdfParagraph::new(body.name.clone()))
+                .element(PdfParagraph::new(format!("{:.3}", body.perimeter())).aligned(Alignment::Right))
+                .element(PdfParagraph::new(body.quantity.to_string()).aligned(Alignment::Right))
+                .element(PdfParagraph::new(format!("{:.3}", body.extended_perimeter())).aligned(Alignment::Right))
+                .element(PdfParagraph::new(format!("{:.3}", body.extended_toolpath_perimeter())).aligned(Alignment::Right))
+                .element(PdfParagraph::new((body.pierces() * body.quantity).to_string()).aligned(Alignment::Right))
+                .push()?;
+        }
+        let total_perimeter: f64 = bodies.iter().map(|body| body.extended_perimeter()).sum();
+        let total_toolpath: f64 = bodies.iter().map(|body| body.extended_toolpath_perimeter()).sum();
+        let total_pierces: usize = bodies.iter().map(|body| body.pierces() * body.quantity).sum();
+        table.row()
+            .element(PdfParagraph::new("Total").styled(heading))
+            .element(PdfParagraph::new(""))
+            .element(PdfParagraph::new(total_parts.to_string()).aligned(Alignment::Right).styled(heading))
+            .element(PdfParagraph::new(format!("{:.3}", total_perimeter)).aligned(Alignment::Right).styled(heading))
+            .element(PdfParagraph::new(format!("{:.3}", total_toolpath)).aligned(Alignment::Right).styled(heading))
+            .element(PdfParagraph::new(total_pierces.to_string()).aligned(Alignment::Right).styled(heading))
+            .push()?;
+        pdf.push(table);
+
+        // body sections
+        for body in bodies {
+            pdf.push(PageBreak::new());
+            pdf.push(PdfParagraph::new(body.name.clone()).styled(Style::new().bold().with_font_size(16)));
+            pdf.push(Break::new(1));
+            for line in summary_lines(body) { pdf.push(PdfParagraph::new(line)); }
+            pdf.push(PdfParagraph::new(format!("extended perimeter: {:.3}", body.extended_perimeter())));
+            if body.kerf > 0.0 { pdf.push(PdfParagraph::new(format!("extended toolpath perimeter: {:.3}", body.extended_toolpath_perimeter()))); }
+            pdf.push(PdfParagraph::new(format!("pierces: {}", body.pierces())));
+            for feature in &body.features {
+                pdf.push(Break::new(1));
+                for line in feature.summarize() { pdf.push(PdfParagraph::new(line)); }
+            }
+        }
+
+        // saving
+        let path = format!("./{}.pdf", project);
+        pdf.render_to_file(&path)?;
+        Ok(path)
+    }
+
+
+
+    /// Decorates project report pages with margins, a header with the project name and a footer with the page number.
+    struct ReportPageDecorator {
+        /// The name of the project shown in the header.
+        project: String,
+        /// The number of the page being decorated.
+        page: usize,
+    }
+    impl ReportPageDecorator {
+        /// The page margins in millimeters.
+        const MARGINS: i32 = 10;
+
+        /// Creates a new report page decorator.
+        fn new(project: String) -> Self { Self { project, page: 0 } }
+    }
+    impl PageDecorator for ReportPageDecorator {
+        fn decorate_page<'a>(&mut self, context: &Context, mut area: Area<'a>, style: Style) -> Result<Area<'a>, genpdf::error::Error> {
+            self.page += 1;
+            area.add_margins(Self::MARGINS);
+            let small = style.with_font_size(8);
+            let line_height = small.line_height(&context.font_cache);
+
+            // footer
+            let mut footer_area = area.clone();
+            footer_area.add_offset(Position::new(0, area.size().height - line_height));
+            PdfParagraph::new(format!("Page {}", self.page))
+                .aligned(Alignment::Center)
+                .render(context, footer_area, small)?;
+            area.set_height(area.size().height - line_height * 2.0);
+
+            // header (the cover page has none)
+            if self.page > 1 {
+                let header = PdfParagraph::new(self.project.clone())
+                    .aligned(Alignment::Right)
+                    .render(context, area.clone(), small)?;
+                area.add_offset(Position::new(0, header.size.height + line_height));
+            }
+
+            Ok(area)
+        }
+    }
+}
+
+
+
+/// Csv exports and imports of a body's feature list.
+/// Each row is one feature: type, parameters by name, count, per-unit value and total value.
+/// The first row after the header describes the body itself.
+pub mod csv {
+    use crate::export::file_stem;
+    use crate::shapes::{Body, BodyShape, Features, Placement, StepKind};
+
+    /// The header row of every csv export.
+    const HEADER: &str = "type,parameters,count,unit value,total value";
+    /// The characters that separate a row's parameters, which a body name cannot contain.
+    pub const RESERVED_CHARACTERS: [char; 2] = [';', '='];
+
+    /// Returns the csv text for the body.
+    pub fn body_to_csv(body: &Body) -> String {
+        let mut rows = Vec::new();
+        rows.push(HEADER.to_string());
+
+        // the body row
+        let base_perimeter = body.base_perimeter();
+        let shape_parameters = match body.shape {
+            BodyShape::Annulus { inner_diameter } => { format!("; inner diameter={}", inner_diameter) }
+            BodyShape::RegularPolygon { sides } => { format!("; sides={}", sides) }
+            _ => { "".to_string() }
+        };
+        let kerf = if body.kerf > 0.0 { format!("; kerf={}", body.kerf) } else { "".to_string() };
+        let body_parameters = format!(
+            "name={}; shape={}; width={}; height={}{}; quantity={}{}",
+            &body.name, body.shape.name(), body.width, body.height, shape_parameters, body.quantity, kerf
+        );
+        rows.push(row(&["body".to_string(), body_parameters, "1".to_string(), format!("{:.3}", base_perimeter), format!("{:.3}", base_perimeter)]));
+
+        // the feature rows
+        for feature in &body.features {
+            let mut parameters = feature.parameters();
+            if !feature.placements().is_empty() {
+                parameters.push(("placement".to_string(), Placement::list_text(feature.placements())));
+            }
+            let parameters = parameters
+                .iter()
+                .map(|(field, value)| format!("{}={}", field, value))
+                .collect::<Vec<String>>()
+                .join("; ");
+            let unit_value = if feature.count() == 0 { 0.0 } else { feature.value() / feature.count() as f64 };
+            rows.push(row(&[
+                feature.shape().name(),
+                parameters,
+                feature.count().to_string(),
+                format!("{:.3}", unit_value),
+                format!("{:.3}", feature.value()),
+            ]));
+        }
+
+        rows.join("\n") + "\n"
+    }
+
+    /// Parses csv text into a new body.
+    /// Features are rebuilt through their addition paths so they follow the same rules as features entered by hand.
+    /// Every malformed row is reported with its line number.
+    pub fn body_from_csv(text: &str) -> Result<Body, Vec<String>> {
+        let mut body = Body::new();
+        let mut errors = Vec::new();
+        let mut has_body_row = false;
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            if line.trim().is_empty() || line.trim() == HEADER { continue; }
+
+            let result = split_row(line).and_then(|fields| {
+                if fields.len() < 3 { return Err("expected at least type, parameters and count".to_string()); }
+                let parameters = split_parameters(&fields[1])?;
+
+                // the body row
+                if fields[0].trim() == "body" {
+                    if has_body_row { return Err("the body is defined more than once".to_string()); }
+                    has_body_row = true;
+                    return read_body_row(&mut body, &parameters);
+                }
+
+                // a feature row
+                let feature = Features::from_name(&fields[0]).ok_or(format!("unknown feature type \"{}\"", fields[0].trim()))?;
+                read_feature_row(&mut body, feature, &parameters, fields[2].trim())
+            });
+
+            if let Err(error) = result { errors.push(format!("line {}: {}", line_number, error)); }
+        }
+
+        if !has_body_row { errors.push("no body row was found".to_string()); }
+
+        if errors.is_empty() { Ok(body) } else { Err(errors) }
+    }
+
+    /// Sets the name, shape and dimensions of the body from the body row's parameters.
+    /// Rows without a shape are rectangles.
+    fn read_body_row(body: &mut Body, parameters: &[(String, String)]) -> Result<(), String> {
+        let mut shape = BodyShape::Rectangle;
+        let mut inner_diameter = None;
+        let mut sides = None;
+        for (field, value) in parameters {
+            match field.as_str() {
+                "name" => { body.rename(value.clone()); }
+                "shape" => { shape = BodyShape::from_name(value).ok_or(format!("unknown body shape \"{}\"", value))?; }
+                "inner diameter" => { inner_diameter = Some(parse_positive(field, value)?); }
+                "sides" => { sides = Some(value.parse::<usize>().map_err(|_| format!("\"{}\" is not a valid number of sides", value))?); }
+                "width" => { body.set_width(parse_positive(field, value)?); }
+                "height" => { body.set_height(parse_positive(field, value)?); }
+                "quantity" => {
+                    let quantity = value.parse::<usize>().map_err(|_| format!("\"{}\" is not a valid quantity", value))?;
+                    body.set_quantity(quantity);
+                }
+                "kerf" => {
+                    match value.parse::<f64>() {
+                        Ok(kerf) if kerf >= 0.0 => { body.set_kerf(kerf); }
+                        _ => { return Err(format!("\"{}\" is not a valid kerf", value)); }
+                    }
+                }
+                _ => { return Err(format!("unknown body parameter \"{}\"", field)); }
+            }
+        }
+        if body.name.is_empty() { return Err("the body has no name".to_string()); }
+        if body.width <= 0.0 || body.height <= 0.0 { return Err("the body needs a width and a height".to_string()); }
+
+        // the shape's own dimensions
+        let second = match shape {
+            BodyShape::Annulus { .. } => { inner_diameter.ok_or("an annulus needs an inner diameter")? }
+            BodyShape::RegularPolygon { .. } => { sides.ok_or("a regular polygon needs a number of sides")? as f64 }
+            _ => { body.height }
+        };
+        shape.check_dimensions(body.width, second)?;
+        let width = body.width;
+        body.set_shape_dimensions(shape, width, second);
+        Ok(())
+    }
+
+    /// Adds a feature to the body by walking its addition path with the row's values.
+    fn read_feature_row(body: &mut Body, feature: Features, parameters: &[(String, String)], count: &str) -> Result<(), String> {
+        let mut path = feature.path();
+        let parameters = &upgrade_parameters(&feature, parameters);
+
+        // every parameter must belong to a step of the feature
+        for (field, _) in parameters {
+            if !path.fields().contains(field) { return Err(format!("unknown {} parameter \"{}\"", feature.name(), field)); }
+        }
+
+        loop {
+            let field = path.current_step_value();
+            let input = if field == "count" {
+                count.to_string()
+            }
+            else {
+                parameters
+                    .iter()
+                    .find(|(name, _)| name == &field)
+                    .map(|(_, value)| value.clone())
+                    .or(optional_default(&feature, &field).map(|default| default.to_string()))
+                    .ok_or(format!("missing {} parameter \"{}\"", feature.name(), field))?
+            };
+            check_input(path.current_step_kind(), &field, &input)?;
+
+            path.update_current_step_value_input(input);
+            if let Some(new_feature) = path.finish_current_step() {
+                body.check_fit(new_feature.as_ref())?;
+                body.add(new_feature);
+                return Ok(());
+            }
+            if let Some(error) = &path.error { return Err(error.clone()); }
+        }
+    }
+
+    /// Checks a value against what its step's numpad could have entered.
+    /// Numbers cannot be negative unless the step is signed, and counts are whole numbers of at least one.
+    fn check_input(kind: StepKind, field: &str, input: &str) -> Result<(), String> {
+        if kind == StepKind::Text { return Ok(()); }
+        let number = match input.parse::<f64>() {
+            Ok(number) if number.is_finite() => { number }
+            _ => { return Err(format!("\"{}\" is not a number for {}", input, field)); }
+        };
+        if kind == StepKind::Number && number < 0.0 { return Err(format!("{} cannot be negative, got \"{}\"", field, input)); }
+        if field == "count" && (number < 1.0 || number.fract() != 0.0) { return Err(format!("\"{}\" is not a valid count", input)); }
+        Ok(())
+    }
+
+    /// Renames the parameters of rows written before a feature's parameters changed.
+    /// A chamfer's single size became two legs, so an old size is read as a symmetric chamfer.
+    fn upgrade_parameters(feature: &Features, parameters: &[(String, String)]) -> Vec<(String, String)> {
+        let mut upgraded = Vec::new();
+        for (field, value) in parameters {
+            match (feature, field.as_str()) {
+                (Features::ChamferFeature, "size") => {
+                    upgraded.push(("leg".to_string(), value.clone()));
+                    upgraded.push(("second size".to_string(), value.clone()));
+                }
+                _ => { upgraded.push((field.clone(), value.clone())); }
+            }
+        }
+        upgraded
+    }
+
+    /// Returns the value of a parameter that a row may leave out.
+    /// Unplaced features have no placement, and steps added to a feature after it could first be exported
+    /// default to the feature's old shape so that older files still import.
+    fn optional_default(feature: &Features, field: &str) -> Option<&'static str> {
+        match (feature, field) {
+            (_, "placement") => { Some("") }
+            (Features::RectangularHoleFeature, "corner radius") => { Some("0") }
+            (Features::RectangularHoleFeature, "corner style") => { Some("0") }
+            (Features::NotchFeature, "width") => { Some("0") }
+            (Features::NotchFeature, "wall angle") => { Some("0") }
+            (Features::NotchFeature, "bottom style") => { Some("0") }
+            (Features::NotchFeature, "relief radius") => { Some("0") }
+            (Features::NotchFeature, "relief style") => { Some("0") }
+            (Features::ChamferFeature, "second size type") => { Some("0") }
+            _ => { None }
+        }
+    }
+
+    /// Parses a dimension that must be greater than zero.
+    fn parse_positive(field: &str, value: &str) -> Result<f64, String> {
+        match value.parse::<f64>() {
+            Ok(number) if number > 0.0 => Ok(number),
+            _ => Err(format!("\"{}\" is not a valid {}", value, field)),
+        }
+    }
+
+    /// Splits a parameter list like "width=10; height=5" into its names and values.
+    fn split_parameters(parameters: &str) -> Result<Vec<(String, String)>, String> {
+        let mut pairs = Vec::new();
+        for parameter in parameters.split(';') {
+            if parameter.trim().is_empty() { continue; }
+            let (field, value) = parameter.split_once('=').ok_or(format!("parameter \"{}\" has no value", parameter.trim()))?;
+            pairs.push((field.trim().to_string(), value.trim().to_string()));
+        }
+        Ok(pairs)
+    }
+
+    /// Joins fields into a csv row, quoting any field that needs it.
+    fn row(fields: &[String]) -> String {
+        fields
+            .iter()
+            .map(|field| {
+                if field.contains(',') || field.contains('"') || field.contains('\n') {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                }
+                else {
+                    field.clone()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    /// Splits a csv row into its fields, respecting quoted fields.
+    fn split_row(line: &str) -> Result<Vec<String>, String> {
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut chars = line.chars().peekable();
+
+        while let Some(char) = chars.next() {
+            match char {
+                '"' if in_quotes => {
+                    // a doubled quote is an escaped quote
+                    if chars.peek() == Some(&'"') {
+                        field.push('"');
+                        chars.next();
+                    }
+                    else { in_quotes = false; }
+                }
+                '"' if field.is_empty() => { in_quotes = true; }
+                ',' if !in_quotes => { fields.push(std::mem::take(&mut field)); }
+                _ => { field.push(char); }
+            }
+        }
+        if in_quotes { return Err("unclosed quote".to_string()); }
+        fields.push(field);
+
+        Ok(fields)
+    }
+
+    /// Saves a csv export of the body and returns the path of the new file.
+    pub fn print_body(body: &Body, project: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let path = format!("./{}.csv", file_stem(project, body));
+        std::fs::write(&path, body_to_csv(body))?;
+        Ok(path)
+    }
+
+    /// Reads a body from a csv file.
+    pub fn import_body(path: &str) -> Result<Body, Vec<String>> {
+        let text = std::fs::read_to_string(path.trim()).map_err(|error| vec![format!("could not read {}: {}", path.trim(), error)])?;
+        body_from_csv(&text)
+    }
+}
+
+
+
+/// Markdown report exports.
+pub mod markdown {
+    use crate::export::{feature_table, file_stem, summary_lines};
+    use crate::shapes::Body;
+
+    /// Returns a markdown report of the body.
+    pub fn body_report(body: &Body, project: &str) -> String {
+        let mut lines = Vec::new();
+
+        // header
+        lines.push(format!("# {} - {}", escape(project), escape(&body.name)));
+        lines.push("".to_string());
+        for line in summary_lines(body) { lines.push(format!("- {}", escape(&line))); }
+
+        // features
+        lines.push("".to_string());
+        lines.push("## Features".to_string());
+        lines.push("".to_string());
+        if body.features.is_empty() {
+            lines.push("No features.".to_string());
+        }
+        else {
+            lines.push("| Feature | Count | Dimensions | Modification |".to_string());
+            lines.push("| --- | ---: | --- | ---: |".to_string());
+            for [name, count, dimensions, modification] in feature_table(body) {
+                lines.push(format!("| {} | {} | {} | {} |", escape(&name), count, escape(&dimensions), modification));
+            }
+        }
+
+        // perimeter breakdown
+        lines.push("".to_string());
+        lines.push("## Perimeter".to_string());
+        lines.push("".to_string());
+        lines.push("| Part | Perimeter |".to_string());
+        lines.push("| --- | ---: |".to_string());
+        for (part, value) in body.perimeter_breakdown() {
+            lines.push(format!("| {} | {:.3} |", escape(&part), value));
+        }
+        lines.push(format!("| **total** | **{:.3}** |", body.perimeter()));
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Escapes the characters that would break a markdown table.
+    fn escape(text: &str) -> String {
+        text.replace('|', "\\|")
+    }
+
+    /// Saves a markdown report of the body and returns the path of the new file.
+    pub fn print_body_report(body: &Body, project: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let path = format!("./{}.md", file_stem(project, body));
+        std::fs::write(&path, body_report(body, project))?;
+        Ok(path)
+    }
+}
+
+
+
+/// Standalone html report exports.
+pub mod html {
+    use crate::export::{feature_table, file_stem, summary_lines, svg};
+    use crate::geometry::Point;
+    use crate::shapes::Body;
+
+    /// The size of the longest side of the part drawing in pixels.
+    const DRAWING_SIZE: f64 = 360.0;
+    /// The space around the part drawing in pixels.
+    const DRAWING_MARGIN: f64 = 40.0;
+
+    /// Returns a standalone html report of the body.
+    pub fn body_report(body: &Body, project: &str) -> String {
+        let title = format!("{} - {}", escape(project), escape(&body.name));
+        let mut html = String::new();
+
+        // document head
+        html += "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n";
+        html += &format!("<title>{}</title>\n", title);
+        html += "<style>\n";
+        html += "body { font-family: sans-serif; margin: 2em; }\n";
+        html += "table { border-collapse: collapse; margin-bottom: 1.5em; }\n";
+        html += "th, td { border: 1px solid #999; padding: 0.3em 0.8em; text-align: left; }\n";
+        html += "td.number { text-align: right; font-family: monospace; }\n";
+        html += "</style>\n</head>\n<body>\n";
+
+        // header
+        html += &format!("<h1>{}</h1>\n<ul>\n", title);
+        for line in summary_lines(body) { html += &format!("<li>{}</li>\n", escape(&line)); }
+        html += "</ul>\n";
+
+        // drawing
+        html += "<h2>Part</h2>\n";
+        html += &part_svg(body);
+
+        // features
+        html += "<h2>Features</h2>\n";
+        if body.features.is_empty() {
+            html += "<p>No features.</p>\n";
+        }
+        else {
+            html += "<table>\n<tr><th>Feature</th><th>Count</th><th>Dimensions</th><th>Modification</th></tr>\n";
+            for [name, count, dimensions, modification] in feature_table(body) {
+                html += &format!(
+                    "<tr><td>{}</td><td class=\"number\">{}</td><td>{}</td><td class=\"number\">{}</td></tr>\n",
+                    escape(&name), count, escape(&dimensions), modification
+                );
+            }
+            html += "</table>\n";
+        }
+
+        // perimeter breakdown
+        html += "<h2>Perimeter</h2>\n<table>\n<tr><th>Part</th><th>Perimeter</th></tr>\n";
+        for (part, value) in body.perimeter_breakdown() {
+            html += &format!("<tr><td>{}</td><td class=\"number\">{:.3}</td></tr>\n", escape(&part), value);
+        }
+        html += &format!("<tr><th>total</th><th class=\"number\">{:.3}</th></tr>\n", body.perimeter());
+        html += "</table>\n</body>\n</html>\n";
+
+        html
+    }
+
+    /// Returns an inline svg drawing of the body's geometry with its overall dimensions.
+    /// Only placed features are drawn, because the rest have no position on the body.
+    fn part_svg(body: &Body) -> String {
+        let geometry = body.geometry();
+        let scale = DRAWING_SIZE / body.width.max(body.height).max(f64::EPSILON);
+        let width = body.width * scale;
+        let height = body.height * scale;
+        let canvas_width = width + DRAWING_MARGIN * 2.0;
+        let canvas_height = height + DRAWING_MARGIN * 2.0;
+        let place = |point: Point| (DRAWING_MARGIN + point.x * scale, DRAWING_MARGIN + height - point.y * scale);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.1} {:.1}\">\n",
+            canvas_width, canvas_height, canvas_width, canvas_height
+        );
+        svg += &format!(
+            "<path d=\"{}\" fill=\"#eef\" fill-rule=\"evenodd\" stroke=\"#000\" stroke-width=\"2\"/>\n",
+            svg::path_data(&geometry, &place, scale)
+        );
+        svg += &format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" font-size=\"14\">{:.3}</text>\n",
+            DRAWING_MARGIN + width / 2.0, DRAWING_MARGIN - 10.0, body.width
+        );
+        svg += &format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" font-size=\"14\" transform=\"rotate(-90 {:.1} {:.1})\">{:.3}</text>\n",
+            DRAWING_MARGIN - 10.0, DRAWING_MARGIN + height / 2.0, DRAWING_MARGIN - 10.0, DRAWING_MARGIN + height / 2.0, body.height
+        );
+        svg += "</svg>\n";
+        svg
+    }
+
+    /// Escapes the characters that have a meaning in html.
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    /// Saves an html report of the body and returns the path of the new file.
+    pub fn print_body_report(body: &Body, project: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let path = format!("./{}.html", file_stem(project, body));
+        std::fs::write(&path, body_report(body, project))?;
+        Ok(path)
+    }
+}
+
+
+
+/// Plain text exports that line up in monospace fonts, for pasting into emails or other programs.
+pub mod text {
+    use crate::export::{file_stem, summary_lines};
+    use crate::shapes::Body;
+
+    /// Returns a compact, aligned text summary of the body and its features.
+    pub fn body_summary(body: &Body, project: &str) -> String {
+        // the body and feature blocks, each as a title and a list of fields
+        let mut blocks: Vec<(String, Vec<(String, String)>)> = Vec::new();
+        blocks.push((format!("{} - {}", project, &body.name), split_fields(&summary_lines(body))));
+        for feature in &body.features {
+            let mut lines = feature.summarize();
+            let title = lines.remove(0);
+            let lines: Vec<String> = lines.iter().map(|line| line.trim_end_matches(", ").to_string()).collect();
+            blocks.push((title, split_fields(&lines)));
+        }
+
+        // every field name is padded to the longest one so the values line up
+        let key_width = blocks
+            .iter()
+            .flat_map(|(_, fields)| fields.iter().map(|(key, _)| key.chars().count()))
+            .max()
+            .unwrap_or(0);
+
+        let mut text = Vec::new();
+        for (index, (title, fields)) in blocks.iter().enumerate() {
+            if index > 0 { text.push("".to_string()); }
+            text.push(title.clone());
+            for (key, value) in fields {
+                if key.is_empty() { text.push(format!("  {}", value)); }
+                else { text.push(format!("  {:<width$} : {}", key, value, width = key_width)); }
+            }
+        }
+
+        text.join("\n") + "\n"
+    }
+
+    /// Splits summary lines like "width: 10.000" into their names and values.
+    fn split_fields(lines: &[String]) -> Vec<(String, String)> {
+        lines
+            .iter()
+            .map(|line| match line.split_once(": ") {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => ("".to_string(), line.clone()),
+            })
+            .collect()
+    }
+
+    /// Saves a text summary of the body and returns the path of the new file.
+    pub fn print_body_summary(body: &Body, project: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let path = format!("./{}.txt", file_stem(project, body));
+        std::fs::write(&path, body_summary(body, project))?;
+        Ok(path)
+    }
+}
+
+
+
+/// Svg drawings of the body's geometry.
+pub mod svg {
+    use crate::export::file_stem;
+    use crate::geometry::{BodyGeometry, Point, Segment};
+    use crate::shapes::Body;
+
+    /// Returns svg path data for every contour, with each point moved onto the drawing by the given function.
+    /// The function should flip the y axis, because svg drawings measure y downward.
+    pub fn path_data(geometry: &BodyGeometry, place: &dyn Fn(Point) -> (f64, f64), scale: f64) -> String {
+        let mut data = Vec::new();
+        for contour in geometry.contours() {
+            let Some(first) = contour.segments.first() else { continue; };
+            let (x, y) = place(first.start());
+            data.push(format!("M {:.4} {:.4}", x, y));
+            for segment in &contour.segments {
+                match segment {
+                    Segment::Line { end, .. } => {
+                        let (x, y) = place(*end);
+                        data.push(format!("L {:.4} {:.4}", x, y));
+                    }
+                    Segment::Arc { center, radius, start_angle, sweep } => {
+                        // arcs are drawn in halves so a full circle still has two distinct ends and no half is a large arc
+                        for half in 1..=2 {
+                            let (x, y) = place(Point::on_circle(*center, *radius, start_angle + (sweep * half as f64 / 2.0)));
+                            // a counterclockwise arc turns clockwise once y is flipped
+                            let direction = if *sweep > 0.0 { 0 } else { 1 };
+                            data.push(format!("A {:.4} {:.4} 0 0 {} {:.4} {:.4}", radius * scale, radius * scale, direction, x, y));
+                        }
+                    }
+                }
+            }
+            data.push("Z".to_string());
+        }
+        data.join(" ")
+    }
+
+    /// Returns a standalone svg drawing of the body at its real size, in the body's units.
+    pub fn drawing(body: &Body) -> String {
+        let geometry = body.geometry();
+        let (low, high) = geometry.bounds().unwrap_or((Point::new(0.0, 0.0), Point::new(body.width, body.height)));
+        let margin = (high.x - low.x).max(high.y - low.y) * 0.05;
+        let (width, height) = ((high.x - low.x) + (2.0 * margin), (high.y - low.y) + (2.0 * margin));
+        let place = |point: Point| (point.x - low.x + margin, high.y - point.y + margin);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.4}\" height=\"{:.4}\" viewBox=\"0 0 {:.4} {:.4}\">\n",
+            width, height, width, height
+        );
+        svg += &format!("<title>{}</title>\n", body.name.replace('&', "&amp;").replace('<', "&lt;"));
+        for missing in &geometry.missing { svg += &format!("<!-- not drawn: {} -->\n", missing); }
+        svg += &format!(
+            "<path d=\"{}\" fill=\"none\" stroke=\"#000\" stroke-width=\"{:.4}\"/>\n",
+            path_data(&geometry, &place, 1.0), (width.max(height) / 500.0)
+        );
+        svg += "</svg>\n";
+        svg
+    }
+
+    /// Saves an svg drawing of the body and returns the path of the new file.
+    pub fn print_drawing(body: &Body, project: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let path = format!("./{}.svg", file_stem(project, body));
+        std::fs::write(&path, drawing(body))?;
+        Ok(path)
+    }
+}
+
+
+
+/// Dxf drawings of the body's geometry, as plain lines, arcs and circles that most cad and cam programs read.
+pub mod dxf {
+    use std::f64::consts::PI;
+    use crate::export::file_stem;
+    use crate::geometry::Segment;
+    use crate::shapes::Body;
+
+    /// Returns an ascii dxf with the outline on the OUTLINE layer and the holes on the HOLES layer.
+    pub fn drawing(body: &Body) -> String {
+        let geometry = body.geometry();
+        let mut lines: Vec<String> = Vec::new();
+        let mut pair = |code: i32, value: String| { lines.push(code.to_string()); lines.push(value); };
+
+        for missing in &geometry.missing { pair(999, format!("not drawn: {}", missing)); }
+        pair(0, "SECTION".to_string());
+        pair(2, "ENTITIES".to_string());
+        for (index, contour) in geometry.contours().iter().enumerate() {
+            let layer = if index == 0 { "OUTLINE" } else { "HOLES" };
+            for segment in &contour.segments {
+                match segment {
+                    Segment::Line { start, end } => {
+                        pair(0, "LINE".to_string());
+                        pair(8, layer.to_string());
+                        pair(10, format!("{:.6}", start.x));
+                        pair(20, format!("{:.6}", start.y));
+                        pair(11, format!("{:.6}", end.x));
+                        pair(21, format!("{:.6}", end.y));
+                    }
+                    Segment::Arc { center, radius, start_angle, sweep } => {
+                        let full = sweep.abs() >= (2.0 * PI) - 1e-9;
+                        pair(0, if full { "CIRCLE" } else { "ARC" }.to_string());
+                        pair(8, layer.to_string());
+                        pair(10, format!("{:.6}", center.x));
+                        pair(20, format!("{:.6}", center.y));
+                        pair(40, format!("{:.6}", radius));
+                        if !full {
+                            // dxf arcs always run counterclockwise
+                            let (from, to) = if *sweep >= 0.0 { (*start_angle, start_angle + sweep) } else { (start_angle + sweep, *start_angle) };
+                            pair(50, format!("{:.6}", from.to_degrees().rem_euclid(360.0)));
+                            pair(51, format!("{:.6}", to.to_degrees().rem_euclid(360.0)));
+                        }
+                    }
+                }
+            }
+        }
+        pair(0, "ENDSEC".to_string());
+        pair(0, "EOF".to_string());
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Saves a dxf drawing of the body and returns the path of the new file.
+    pub fn print_drawing(body: &Body, project: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let path = format!("./{}.dxf", file_stem(project, body));
+        std::fs::write(&path, drawing(body))?;
+        Ok(path)
+    }
+}
+
+
+
+/// G-code exports that trace the body's geometry.
+/// The path follows the nominal contours, cutting the holes before the outline; no cutter compensation is emitted,
+/// so the machine has to offset the tool by half the kerf itself.
+pub mod gcode {
+    use crate::export::file_stem;
+    use crate::geometry::Segment;
+    use crate::shapes::Body;
+
+    /// The units a g-code program is read in, which must match the units the body was measured in.
+    #[derive(Copy, Clone, PartialEq)]
+    pub enum GcodeUnits {
+        Millimeters,
+        Inches,
+    }
+    impl GcodeUnits {
+        /// Returns the display name of the units.
+        pub fn name(&self) -> String {
+            match self {
+                GcodeUnits::Millimeters => { "millimeters".to_string() }
+                GcodeUnits::Inches => { "inches".to_string() }
+            }
+        }
+
+        /// Returns the other units.
+        pub fn next(&self) -> GcodeUnits {
+            match self {
+                GcodeUnits::Millimeters => { GcodeUnits::Inches }
+                GcodeUnits::Inches => { GcodeUnits::Millimeters }
+            }
+        }
+
+        /// Returns the word that selects the units.
+        fn code(&self) -> &'static str {
+            match self {
+                GcodeUnits::Millimeters => { "G21" }
+                GcodeUnits::Inches => { "G20" }
+            }
+        }
+
+        /// Returns the placeholder feed rate, plunge rate and safe height in these units.
+        /// They are only starting values and must be set for the machine and material before cutting.
+        fn placeholders(&self) -> (f64, f64, f64) {
+            match self {
+                GcodeUnits::Millimeters => { (1000.0, 300.0, 5.0) }
+                GcodeUnits::Inches => { (40.0, 12.0, 0.2) }
+            }
+        }
+    }
+
+    /// Returns a g-code program that lifts to a safe height, then rapids to each contour, plunges to the cut height at Z0,
+    /// cuts the contour with lines and arcs and lifts again.
+    pub fn program(body: &Body, project: &str, units: GcodeUnits) -> String {
+        let geometry = body.geometry();
+        let (feed, plunge, safe_height) = units.placeholders();
+        let mut lines = vec![
+            format!("({} - {})", project, body.name),
+            format!("(units: {})", units.name()),
+            format!("(placeholders: feed F{}, plunge F{}, safe height Z{}, cut height Z0 - set them for the machine and material)", number(feed), number(plunge), number(safe_height)),
+            "(no cutter compensation: the path is the nominal contour, so offset the tool by half the kerf on the machine or the outline comes out small and the holes large)".to_string(),
+            format!("G90 G17 {}", units.code()),
+            format!("G0 Z{}", number(safe_height)),
+        ];
+        for missing in &geometry.missing { lines.push(format!("(not drawn: {})", missing)); }
+        if body.kerf > 0.0 {
+            let (toolpath, exact) = body.toolpath_perimeter();
+            lines.push(format!("(kerf {}: nominal perimeter {:.3}, toolpath perimeter {:.3}{})", number(body.kerf), body.perimeter(), toolpath, if exact { "" } else { " approximate" }));
+        }
+
+        let contours = geometry.holes.iter().chain(std::iter::once(&geometry.outline));
+        for (index, contour) in contours.enumerate() {
+            let Some(first) = contour.segments.first() else { continue; };
+            let label = if index < geometry.holes.len() { format!("hole {}", index + 1) } else { "outline".to_string() };
+            lines.push(format!("({})", label));
+            lines.push(format!("G0 X{} Y{}", number(first.start().x), number(first.start().y)));
+            lines.push("M3".to_string());
+            lines.push(format!("G1 Z0.0000 F{}", number(plunge)));
+            for (segment_index, segment) in contour.segments.iter().enumerate() {
+                // the feed rate is modal, so it is set once the plunge is done
+                let feed_word = if segment_index == 0 { format!(" F{}", number(feed)) } else { "".to_string() };
+                match segment {
+                    Segment::Line { end, .. } => { lines.push(format!("G1 X{} Y{}{}", number(end.x), number(end.y), feed_word)); }
+                    Segment::Arc { center, sweep, .. } => {
+                        let (start, end) = (segment.start(), segment.end());
+                        let command = if *sweep > 0.0 { "G3" } else { "G2" };
+                        lines.push(format!("{} X{} Y{} I{} J{}{}", command, number(end.x), number(end.y), number(center.x - start.x), number(center.y - start.y), feed_word));
+                    }
+                }
+            }
+            lines.push("M5".to_string());
+            lines.push(format!("G0 Z{}", number(safe_height)));
+        }
+        lines.push("M30".to_string());
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Formats a coordinate, without a sign on values that round to zero.
+    fn number(value: f64) -> String {
+        let text = format!("{:.4}", value);
+        if text.trim_start_matches('-').chars().all(|character| character == '0' || character == '.') { "0.0000".to_string() } else { text }
+    }
+
+    /// Saves a g-code program for the body and returns the path of the new file.
+    pub fn print_program(body: &Body, project: &str, units: GcodeUnits) -> Result<String, Box<dyn std::error::Error>> {
+        let path = format!("./{}.nc", file_stem(project, body));
+        std::fs::write(&path, program(body, project, units))?;
+        Ok(path)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::export::csv::{body_from_csv, body_to_csv};
+
+    /// A body row for a 100 by 50 plate, which has a perimeter of 300.
+    const BODY_ROW: &str = "body,name=plate; width=100; height=50,1";
+
+    /// Imports a plate with the given feature rows.
+    fn import(rows: &[&str]) -> Result<crate::shapes::Body, Vec<String>> {
+        body_from_csv(&([BODY_ROW].iter().chain(rows).copied().collect::<Vec<&str>>().join("\n") + "\n"))
+    }
+
+    /// Imports a plate that has to be valid.
+    fn import_valid(rows: &[&str]) -> crate::shapes::Body {
+        import(rows).unwrap_or_else(|errors| panic!("unexpected errors: {:?}", errors))
+    }
+
+    /// Imports a plate that has to be rejected and returns its only error.
+    fn import_error(rows: &[&str]) -> String {
+        match import(rows) {
+            Ok(_) => { panic!("expected the rows to be rejected: {:?}", rows) }
+            Err(errors) => {
+                assert_eq!(errors.len(), 1, "expected one error, got {:?}", errors);
+                errors[0].clone()
+            }
+        }
+    }
+
+    /// Checks that two lengths agree to well within what any summary shows.
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn a_body_survives_a_round_trip() {
+        let body = import_valid(&[
+            "circular hole,\"diameter=5; placement=10,10\",2",
+            "fillet,radius=5; placement=TL,1",
+            "notch,width=10; depth=5; wall angle=0; bottom style=0; relief radius=0; relief style=0; placement=top@50,1",
+            "chamfer,leg=2; second size=3; second size type=0,1",
+        ]);
+        let text = body_to_csv(&body);
+        let reimported = body_from_csv(&text).unwrap_or_else(|errors| panic!("unexpected errors: {:?}", errors));
+        assert_eq!(body_to_csv(&reimported), text);
+        assert_eq!(reimported.name, "plate");
+        assert_eq!(reimported.features.len(), 4);
+        assert_close(reimported.perimeter(), body.perimeter());
+    }
+
+    #[test]
+    fn negative_numbers_are_rejected() {
+        assert_eq!(import_error(&["circular hole,diameter=-5,1"]), "line 2: diameter cannot be negative, got \"-5\"");
+        assert_eq!(import_error(&["circular hole,diameter=5,1", "fillet,radius=-5,1"]), "line 3: radius cannot be negative, got \"-5\"");
+        assert_eq!(
+            import_error(&["notch,width=10; depth=-5; wall angle=0; bottom style=0; relief radius=0; relief style=0,1"]),
+            "line 2: depth cannot be negative, got \"-5\"",
+        );
+    }
+
+    #[test]
+    fn signed_numbers_may_be_negative() {
+        let body = import_valid(&["notch,width=10; depth=5; wall angle=-10; bottom style=0; relief radius=0; relief style=0,1"]);
+        assert_eq!(body.features.len(), 1);
+    }
+
+    #[test]
+    fn counts_must_be_whole_and_at_least_one() {
+        assert_eq!(import_error(&["circular hole,diameter=5,2.7"]), "line 2: \"2.7\" is not a valid count");
+        assert_eq!(import_error(&["circular hole,diameter=5,0"]), "line 2: \"0\" is not a valid count");
+    }
+
+    #[test]
+    fn malformed_rows_are_reported_with_their_line_numbers() {
+        assert_eq!(import_error(&["circular hole,diameter=five,1"]), "line 2: \"five\" is not a number for diameter");
+        assert_eq!(import_error(&["circular hole,depth=5,1"]), "line 2: unknown circular hole parameter \"depth\"");
+        assert_eq!(import_error(&["circular hole,,1"]), "line 2: missing circular hole parameter \"diameter\"");
+        assert_eq!(import_error(&["round hole,diameter=5,1"]), "line 2: unknown feature type \"round hole\"");
+        assert_eq!(import_error(&["circular hole,diameter=5"]), "line 2: expected at least type, parameters and count");
+        assert_eq!(import_error(&["circular hole,\"diameter=5,1"]), "line 2: unclosed quote");
+        assert_eq!(import_error(&[BODY_ROW]), "line 2: the body is defined more than once");
+        assert_eq!(body_from_csv("circular hole,diameter=5,1\n").err(), Some(vec!["no body row was found".to_string()]));
+
+        // every bad row is reported, not just the first
+        let errors = import(&["circular hole,diameter=-5,1", "circular hole,diameter=5,1", "fillet,radius=5,0"]).err().unwrap_or_default();
+        assert_eq!(errors, vec!["line 2: diameter cannot be negative, got \"-5\"".to_string(), "line 4: \"0\" is not a valid count".to_string()]);
+    }
+
+    #[test]
+    fn old_chamfer_sizes_import_as_symmetric_chamfers() {
+        let old = import_valid(&["chamfer,size=2,1"]);
+        let new = import_valid(&["chamfer,leg=2; second size=2; second size type=0,1"]);
+        assert_eq!(body_to_csv(&old), body_to_csv(&new));
+        assert_close(old.perimeter(), 300.0 - (4.0 - (2.0 * 2.0_f64.sqrt())));
+    }
+
+    #[test]
+    fn rows_without_later_steps_import_as_their_old_shapes() {
+        // a rectangular hole from before corner radii and reliefs has square corners
+        let hole = import_valid(&["rectangular hole,width=10; height=20,1"]);
+        assert!(body_to_csv(&hole).contains("rectangular hole,width=10; height=20; corner radius=0; corner style=0,1,60.000,60.000"));
+
+        // a notch from before widths, walls and reliefs is a v notch of its depth
+        let old_notch = import_valid(&["notch,depth=5,1"]);
+        let new_notch = import_valid(&["notch,width=0; depth=5; wall angle=0; bottom style=0; relief radius=0; relief style=0,1"]);
+        assert_eq!(body_to_csv(&old_notch), body_to_csv(&new_notch));
+
+        // unplaced features have no placement column
+        let unplaced = import_valid(&["fillet,radius=5,1"]);
+        assert!(unplaced.features[0].placements().is_empty());
+    }
+
+    /// A plate with a top right fillet, which the outline rounds counterclockwise,
+    /// and a round ended slot up from the bottom edge, which it rounds clockwise.
+    const ROUNDED_ROWS: [&str; 2] = [
+        "fillet,radius=5; placement=TR,1",
+        "edge slot,width=10; depth=20; full radius end=1; placement=bottom@30,1",
+    ];
+
+    #[test]
+    fn gcode_arcs_turn_the_right_way_around_their_centers() {
+        let program = crate::export::gcode::program(&import_valid(&ROUNDED_ROWS), "test", crate::export::gcode::GcodeUnits::Millimeters);
+        let lines = program.lines().collect::<Vec<&str>>();
+        // the fillet runs from (100, 45) around (95, 45)
+        assert!(lines.contains(&"G3 X95.0000 Y50.0000 I-5.0000 J0.0000"), "{}", program);
+        // the slot's end runs from (30, 15) over the top of (35, 15)
+        assert!(lines.contains(&"G2 X35.0000 Y20.0000 I5.0000 J0.0000"), "{}", program);
+        assert!(lines.contains(&"G90 G17 G21"), "{}", program);
+    }
+
+    #[test]
+    fn dxf_arcs_run_counterclockwise_even_for_clockwise_sweeps() {
+        let drawing = crate::export::dxf::drawing(&import_valid(&ROUNDED_ROWS));
+        let lines = drawing.lines().collect::<Vec<&str>>();
+        let arcs = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| **line == "ARC")
+            .map(|(index, _)| lines[index + 1..index + 14].to_vec())
+            .collect::<Vec<Vec<&str>>>();
+        // the slot's first quarter sweeps clockwise from 180 to 90 degrees, so the dxf arc runs from 90 to 180
+        assert!(arcs.contains(&vec!["8", "OUTLINE", "10", "35.000000", "20", "15.000000", "40", "5.000000", "50", "90.000000", "51", "180.000000", "0"]), "{}", drawing);
+        // the fillet already sweeps counterclockwise
+        assert!(arcs.contains(&vec!["8", "OUTLINE", "10", "95.000000", "20", "45.000000", "40", "5.000000", "50", "0.000000", "51", "90.000000", "0"]), "{}", drawing);
+    }
+}
@@ -0,0 +1,776 @@
+use std::f64::consts::PI;
+use crate::shapes::{Body, BodyCorner, BodyEdge, BodyShape, Feature, Placement};
+
+/// Lengths shorter than this are treated as zero when joining segments.
+const TOLERANCE: f64 = 1e-9;
+/// How many straight lines stand in for a curve that is not a circular arc, such as an ellipse.
+pub const CURVE_SAMPLES: usize = 256;
+
+
+
+/// A point on the body, measured from the bottom left corner.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+impl Point {
+    /// Creates a new point.
+    pub fn new(x: f64, y: f64) -> Self { Self { x, y } }
+
+    /// Returns the point moved by another point's coordinates.
+    pub fn add(&self, other: Point) -> Point { Point::new(self.x + other.x, self.y + other.y) }
+
+    /// Returns the point moved back by another point's coordinates.
+    pub fn sub(&self, other: Point) -> Point { Point::new(self.x - other.x, self.y - other.y) }
+
+    /// Returns the point scaled away from the origin.
+    pub fn scale(&self, factor: f64) -> Point { Point::new(self.x * factor, self.y * factor) }
+
+    /// Returns the distance to another point.
+    pub fn distance(&self, other: Point) -> f64 { (other.x - self.x).hypot(other.y - self.y) }
+
+    /// Returns the direction from the origin to the point as a unit vector.
+    pub fn unit(&self) -> Point { self.scale(1.0 / self.x.hypot(self.y)) }
+
+    /// Returns the vector turned a quarter turn counterclockwise.
+    pub fn left(&self) -> Point { Point::new(-self.y, self.x) }
+
+    /// Returns the point on a circle at an angle in radians.
+    pub fn on_circle(center: Point, radius: f64, angle: f64) -> Point {
+        Point::new(center.x + (radius * angle.cos()), center.y + (radius * angle.sin()))
+    }
+}
+
+
+
+/// A straight line or circular arc.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Segment {
+    Line { start: Point, end: Point },
+    /// Angles are in radians, counterclockwise from the x axis. A positive sweep runs counterclockwise.
+    Arc { center: Point, radius: f64, start_angle: f64, sweep: f64 },
+}
+impl Segment {
+    /// Returns where the segment starts.
+    pub fn start(&self) -> Point {
+        match self {
+            Segment::Line { start, .. } => { *start }
+            Segment::Arc { center, radius, start_angle, .. } => { Point::on_circle(*center, *radius, *start_angle) }
+        }
+    }
+
+    /// Returns where the segment ends.
+    pub fn end(&self) -> Point {
+        match self {
+            Segment::Line { end, .. } => { *end }
+            Segment::Arc { center, radius, start_angle, sweep } => { Point::on_circle(*center, *radius, start_angle + sweep) }
+        }
+    }
+
+    /// Returns the length of the segment.
+    pub fn length(&self) -> f64 {
+        match self {
+            Segment::Line { start, end } => { start.distance(*end) }
+            Segment::Arc { radius, sweep, .. } => { radius * sweep.abs() }
+        }
+    }
+
+    /// Returns the direction of travel where the segment starts, as a unit vector.
+    pub fn start_direction(&self) -> Point {
+        match self {
+            Segment::Line { start, end } => { end.sub(*start).unit() }
+            Segment::Arc { start_angle, sweep, .. } => { Point::on_circle(Point::new(0.0, 0.0), sweep.signum(), *start_angle).left() }
+        }
+    }
+
+    /// Returns the direction of travel where the segment ends, as a unit vector.
+    pub fn end_direction(&self) -> Point {
+        match self {
+            Segment::Line { start, end } => { end.sub(*start).unit() }
+            Segment::Arc { start_angle, sweep, .. } => { Point::on_circle(Point::new(0.0, 0.0), sweep.signum(), start_angle + sweep).left() }
+        }
+    }
+
+    /// Returns the segment's share of the signed area of a closed contour (positive when the contour runs counterclockwise).
+    pub fn area_term(&self) -> f64 {
+        let (start, end) = (self.start(), self.end());
+        match self {
+            Segment::Line { .. } => { ((start.x * end.y) - (end.x * start.y)) / 2.0 }
+            Segment::Arc { center, radius, sweep, .. } => {
+                ((center.x * (end.y - start.y)) - (center.y * (end.x - start.x)) + (radius * radius * sweep)) / 2.0
+            }
+        }
+    }
+
+    /// Returns the lowest and highest corners of a box around the segment.
+    pub fn bounds(&self) -> (Point, Point) {
+        let (start, end) = (self.start(), self.end());
+        let mut points = vec![start, end];
+        if let Segment::Arc { center, radius, start_angle, sweep } = self {
+            // the arc reaches across an axis wherever it passes a quarter turn
+            let (low, high) = if *sweep >= 0.0 { (*start_angle, start_angle + sweep) } else { (start_angle + sweep, *start_angle) };
+            let mut quarter = (low / (PI / 2.0)).ceil() * (PI / 2.0);
+            while quarter <= high {
+                points.push(Point::on_circle(*center, *radius, quarter));
+                quarter += PI / 2.0;
+            }
+        }
+        let low = Point::new(points.iter().map(|point| point.x).fold(f64::MAX, f64::min), points.iter().map(|point| point.y).fold(f64::MAX, f64::min));
+        let high = Point::new(points.iter().map(|point| point.x).fold(f64::MIN, f64::max), points.iter().map(|point| point.y).fold(f64::MIN, f64::max));
+        (low, high)
+    }
+
+    /// Returns the same segment run the other way.
+    pub fn reversed(&self) -> Segment {
+        match self {
+            Segment::Line { start, end } => { Segment::Line { start: *end, end: *start } }
+            Segment::Arc { center, radius, start_angle, sweep } => { Segment::Arc { center: *center, radius: *radius, start_angle: start_angle + sweep, sweep: -sweep } }
+        }
+    }
+
+    /// Returns the segment moved by an offset.
+    pub fn translated(&self, offset: Point) -> Segment {
+        self.mapped(&Frame { origin: offset, along: Point::new(1.0, 0.0), across: Point::new(0.0, 1.0) })
+    }
+
+    /// Returns the segment moved from a frame's local coordinates onto the body.
+    fn mapped(&self, frame: &Frame) -> Segment {
+        match self {
+            Segment::Line { start, end } => { Segment::Line { start: frame.apply(*start), end: frame.apply(*end) } }
+            Segment::Arc { center, radius, start_angle, sweep } => {
+                let new_center = frame.apply(*center);
+                let start = frame.apply(Point::on_circle(*center, *radius, *start_angle)).sub(new_center);
+                Segment::Arc { center: new_center, radius: *radius, start_angle: start.y.atan2(start.x), sweep: sweep * frame.handedness() }
+            }
+        }
+    }
+}
+
+
+
+/// An orthonormal frame that moves local coordinates onto the body.
+struct Frame {
+    /// Where the local origin sits.
+    origin: Point,
+    /// The direction of the local x axis.
+    along: Point,
+    /// The direction of the local y axis.
+    across: Point,
+}
+impl Frame {
+    /// Moves a local point onto the body.
+    fn apply(&self, point: Point) -> Point {
+        self.origin.add(self.along.scale(point.x)).add(self.across.scale(point.y))
+    }
+
+    /// Moves a point on the body into local coordinates.
+    fn local(&self, point: Point) -> Point {
+        let offset = point.sub(self.origin);
+        Point::new((offset.x * self.along.x) + (offset.y * self.along.y), (offset.x * self.across.x) + (offset.y * self.across.y))
+    }
+
+    /// Returns 1 if the frame keeps counterclockwise turns counterclockwise, or -1 if it mirrors them.
+    fn handedness(&self) -> f64 {
+        if (self.along.x * self.across.y) - (self.along.y * self.across.x) >= 0.0 { 1.0 } else { -1.0 }
+    }
+}
+
+
+
+/// A closed loop of segments, each starting where the last one ended.
+#[derive(Clone, Debug, Default)]
+pub struct Contour {
+    pub segments: Vec<Segment>,
+}
+impl Contour {
+    /// Creates a contour from its segments.
+    pub fn new(segments: Vec<Segment>) -> Self { Self { segments } }
+
+    /// Creates a full circle.
+    pub fn circle(center: Point, radius: f64) -> Self {
+        Self::new(vec![Segment::Arc { center, radius, start_angle: 0.0, sweep: 2.0 * PI }])
+    }
+
+    /// Creates a closed polygon through the points.
+    pub fn polygon(points: &[Point]) -> Self {
+        Self::new(path(points, &vec![CornerCut::Sharp; points.len()], true))
+    }
+
+    /// Returns the length of the contour.
+    pub fn length(&self) -> f64 { self.segments.iter().map(|segment| segment.length()).sum() }
+
+    /// Returns the area inside the contour.
+    pub fn area(&self) -> f64 { self.segments.iter().map(|segment| segment.area_term()).sum::<f64>().abs() }
+
+    /// Returns the length of the contour offset by a distance, outward when positive and inward when negative.
+    /// Arcs grow or shrink with their radius, outside corners are rounded over by the offset and inside corners are trimmed.
+    /// Returns None where the offset is too large for the contour, such as an inside arc or a gap narrower than the offset.
+    pub fn offset_length(&self, distance: f64) -> Option<f64> {
+        if distance.abs() < TOLERANCE { return Some(self.length()); }
+
+        // the offset to the right of the direction of travel
+        let orientation = self.segments.iter().map(|segment| segment.area_term()).sum::<f64>().signum();
+        let right = distance * orientation;
+
+        let mut length = 0.0;
+        let mut trims = vec![0.0; self.segments.len()];
+        for (index, segment) in self.segments.iter().enumerate() {
+            if let Segment::Arc { radius, sweep, .. } = segment {
+                if radius + (right * sweep.signum()) < -TOLERANCE { return None; }
+                length += right * sweep;
+            }
+
+            // the turn into the next segment, positive to the left
+            let next = (index + 1) % self.segments.len();
+            let (from, to) = (segment.end_direction(), self.segments[next].start_direction());
+            let turn = ((from.x * to.y) - (from.y * to.x)).atan2((from.x * to.x) + (from.y * to.y));
+            if turn.abs() < TOLERANCE { continue; }
+            if right * turn > 0.0 {
+                length += right.abs() * turn.abs();
+            }
+            else {
+                if turn.abs() > PI - TOLERANCE { return None; }
+                let trim = right.abs() * (turn.abs() / 2.0).tan();
+                trims[index] += trim;
+                trims[next] += trim;
+                length -= 2.0 * trim;
+            }
+        }
+
+        // a segment trimmed away completely no longer follows the offset
+        if self.segments.iter().zip(&trims).any(|(segment, trim)| *trim > segment.length() + TOLERANCE) { return None; }
+
+        Some(self.length() + length)
+    }
+
+    /// Returns the lowest and highest corners of a box around the contour.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.segments.iter().map(|segment| segment.bounds()).reduce(|(low, high), (segment_low, segment_high)| {
+            (Point::new(low.x.min(segment_low.x), low.y.min(segment_low.y)), Point::new(high.x.max(segment_high.x), high.y.max(segment_high.y)))
+        })
+    }
+
+    /// Returns the contour moved by an offset.
+    pub fn translated(&self, offset: Point) -> Contour {
+        Contour::new(self.segments.iter().map(|segment| segment.translated(offset)).collect())
+    }
+}
+
+
+
+/// How the path turns at one of its corners.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CornerCut {
+    /// A sharp corner.
+    Sharp,
+    /// An arc of the radius tangent to both sides.
+    Round(f64),
+    /// A straight cut with a leg along the side before the corner and a leg along the side after it.
+    Chamfer(f64, f64),
+    /// A half circle of the radius that passes through a square corner, cutting into both sides.
+    DogBone(f64),
+    /// A half circle of the radius centered on one side of a square corner, cutting past the other side.
+    /// The bool picks the side after the corner instead of the side before it.
+    TBone(f64, bool),
+}
+impl CornerCut {
+    /// Returns how far the cut reaches along the side before and the side after a corner that turns by an angle.
+    fn reach(&self, turn: f64) -> (f64, f64) {
+        match self {
+            CornerCut::Sharp => { (0.0, 0.0) }
+            CornerCut::Round(radius) => { let tangent = radius * (turn.abs() / 2.0).tan(); (tangent, tangent) }
+            CornerCut::Chamfer(leg, second_leg) => { (*leg, *second_leg) }
+            CornerCut::DogBone(radius) => { (2.0_f64.sqrt() * radius, 2.0_f64.sqrt() * radius) }
+            CornerCut::TBone(radius, false) => { (2.0 * radius, 0.0) }
+            CornerCut::TBone(radius, true) => { (0.0, 2.0 * radius) }
+        }
+    }
+
+    /// Returns the segments that replace the corner, from where the cut meets the side before to where it meets the side after.
+    fn segments(&self, corner: Point, before: Point, after: Point) -> Vec<Segment> {
+        let turn = turn_angle(before, after);
+        let (reach_before, reach_after) = self.reach(turn);
+        let start = corner.sub(before.scale(reach_before));
+        let end = corner.add(after.scale(reach_after));
+        match self {
+            CornerCut::Sharp => { Vec::new() }
+            CornerCut::Chamfer(..) => { vec![Segment::Line { start, end }] }
+            CornerCut::Round(radius) => {
+                if turn.abs() < TOLERANCE { return Vec::new(); }
+                let center = start.add(before.left().scale(radius * turn.signum()));
+                let from = start.sub(center);
+                vec![Segment::Arc { center, radius: *radius, start_angle: from.y.atan2(from.x), sweep: turn }]
+            }
+            CornerCut::DogBone(_) | CornerCut::TBone(..) => {
+                // a half circle between the two ends that bulges away from the inside of the turn
+                let center = start.add(end).scale(0.5);
+                let radius = start.distance(end) / 2.0;
+                let from = start.sub(center);
+                let start_angle = from.y.atan2(from.x);
+                let outside = match self {
+                    CornerCut::TBone(_, false) => { before.left().scale(-turn.signum()) }
+                    CornerCut::TBone(_, true) => { after.left().scale(-turn.signum()) }
+                    _ => { corner.sub(center) }
+                };
+                let middle = start_angle + (PI / 2.0);
+                let sweep = if (middle.cos() * outside.x) + (middle.sin() * outside.y) >= 0.0 { PI } else { -PI };
+                vec![Segment::Arc { center, radius, start_angle, sweep }]
+            }
+        }
+    }
+}
+
+/// Returns the signed angle a path turns through going from one direction to another (positive to the left).
+fn turn_angle(before: Point, after: Point) -> f64 {
+    ((before.x * after.y) - (before.y * after.x)).atan2((before.x * after.x) + (before.y * after.y))
+}
+
+/// Returns the segments of a path through the points, with each corner cut as given.
+/// An open path leaves its first and last points sharp.
+pub fn path(points: &[Point], cuts: &[CornerCut], closed: bool) -> Vec<Segment> {
+    let count = points.len();
+    if count < 2 { return Vec::new(); }
+
+    // where the path leaves and reaches each corner, after the corner's cut
+    let mut corner_segments: Vec<Vec<Segment>> = vec![Vec::new(); count];
+    let mut leaves: Vec<Point> = points.to_vec();
+    let mut reaches: Vec<Point> = points.to_vec();
+    for index in 0..count {
+        if !closed && (index == 0 || index == count - 1) { continue; }
+        let previous = points[(index + count - 1) % count];
+        let next = points[(index + 1) % count];
+        let before = points[index].sub(previous).unit();
+        let after = next.sub(points[index]).unit();
+        let segments = cuts[index].segments(points[index], before, after);
+        if let (Some(first), Some(last)) = (segments.first(), segments.last()) {
+            reaches[index] = first.start();
+            leaves[index] = last.end();
+        }
+        corner_segments[index] = segments;
+    }
+
+    let mut segments = Vec::new();
+    let sides = if closed { count } else { count - 1 };
+    for (index, leave) in leaves.iter().enumerate().take(sides) {
+        let next = (index + 1) % count;
+        push_line(&mut segments, *leave, reaches[next]);
+        segments.extend(corner_segments[next].iter().copied());
+    }
+    segments
+}
+
+/// Adds a line to the segments unless it has no length.
+fn push_line(segments: &mut Vec<Segment>, start: Point, end: Point) {
+    if start.distance(end) > TOLERANCE { segments.push(Segment::Line { start, end }); }
+}
+
+/// Returns straight lines through the points, standing in for a curve.
+pub fn polyline(points: &[Point]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    for pair in points.windows(2) { push_line(&mut segments, pair[0], pair[1]); }
+    segments
+}
+
+/// Returns points along an ellipse around the origin between two parametric angles.
+pub fn ellipse_points(semi_width: f64, semi_height: f64, start: f64, end: f64, samples: usize) -> Vec<Point> {
+    (0..=samples)
+        .map(|sample| {
+            let angle = start + ((end - start) * sample as f64 / samples as f64);
+            Point::new(semi_width * angle.cos(), semi_height * angle.sin())
+        })
+        .collect()
+}
+
+/// Returns the arc through three points, from the first to the last.
+pub fn arc_through(start: Point, middle: Point, end: Point) -> Segment {
+    // the center is where the perpendicular bisectors of the two chords cross
+    let (a, b, c) = (start, middle, end);
+    let determinant = 2.0 * ((a.x * (b.y - c.y)) + (b.x * (c.y - a.y)) + (c.x * (a.y - b.y)));
+    let square = |point: Point| (point.x * point.x) + (point.y * point.y);
+    let center = Point::new(
+        ((square(a) * (b.y - c.y)) + (square(b) * (c.y - a.y)) + (square(c) * (a.y - b.y))) / determinant,
+        ((square(a) * (c.x - b.x)) + (square(b) * (a.x - c.x)) + (square(c) * (b.x - a.x))) / determinant,
+    );
+    let angle = |point: Point| (point.y - center.y).atan2(point.x - center.x);
+    let start_angle = angle(a);
+    let positive = |target: f64| (target - start_angle).rem_euclid(2.0 * PI);
+    // the arc runs counterclockwise if it reaches the middle point before the end going that way
+    let sweep = if positive(angle(b)) <= positive(angle(c)) { positive(angle(c)) } else { positive(angle(c)) - (2.0 * PI) };
+    Segment::Arc { center, radius: center.distance(a), start_angle, sweep }
+}
+
+
+
+/// A profile cut into one of the body's edges.
+/// Local coordinates run along the edge from the placement's offset and into the body.
+/// The profile starts on the edge and ends at its level, which is how far the rest of the edge has moved into the body.
+#[derive(Clone, Debug, Default)]
+pub struct EdgeProfile {
+    pub segments: Vec<Segment>,
+    pub level: f64,
+}
+impl EdgeProfile {
+    /// Creates a profile that returns to the edge.
+    pub fn new(segments: Vec<Segment>) -> Self { Self { segments, level: 0.0 } }
+
+    /// Creates a profile that steps the rest of the edge into the body (or out of it, for a negative level).
+    pub fn step(segments: Vec<Segment>, level: f64) -> Self { Self { segments, level } }
+}
+
+
+
+/// The shape a feature adds to the body's geometry.
+#[derive(Clone, Debug)]
+pub enum FeatureGeometry {
+    /// A hole outline around its center, repeated at every placed point.
+    Hole(Contour),
+    /// Hole outlines that already sit at their positions on the body.
+    PositionedHoles(Vec<Contour>),
+    /// A cut at every placed corner.
+    Corner(CornerCut),
+    /// A profile at every placed edge offset.
+    Edge(EdgeProfile),
+    /// An outline around the body's center that replaces the body's own outline.
+    Outline(Contour),
+}
+
+
+
+/// The closed contours of a body: its outline and its holes.
+#[derive(Clone, Debug, Default)]
+pub struct BodyGeometry {
+    pub outline: Contour,
+    pub holes: Vec<Contour>,
+    /// A note for every feature instance that could not be drawn.
+    pub missing: Vec<String>,
+}
+impl BodyGeometry {
+    /// Returns every contour, the outline first.
+    pub fn contours(&self) -> Vec<&Contour> {
+        std::iter::once(&self.outline).chain(self.holes.iter()).collect()
+    }
+
+    /// Returns the length of every contour combined.
+    pub fn perimeter(&self) -> f64 { self.contours().iter().map(|contour| contour.length()).sum() }
+
+    /// Returns the length of a cutting tool's path around every contour, outside the outline and inside the holes.
+    /// Returns None if a contour is too small for the tool.
+    pub fn toolpath_length(&self, tool_diameter: f64) -> Option<f64> {
+        let offset = tool_diameter / 2.0;
+        let outline = self.outline.offset_length(offset)?;
+        self.holes.iter().try_fold(outline, |length, hole| Some(length + hole.offset_length(-offset)?))
+    }
+
+    /// Returns the area inside the outline and outside the holes.
+    pub fn area(&self) -> f64 { self.outline.area() - self.holes.iter().map(|hole| hole.area()).sum::<f64>() }
+
+    /// Returns the lowest and highest corners of a box around the outline.
+    pub fn bounds(&self) -> Option<(Point, Point)> { self.outline.bounds() }
+}
+
+
+
+/// Builds the contours of a body from its base shape and the given features, which are usually all of the body's features.
+/// Features, or instances of them, without a placement have no position and are noted as missing instead.
+pub fn body_geometry(body: &Body, features: &[&dyn Feature]) -> BodyGeometry {
+    let mut geometry = BodyGeometry::default();
+    let center = Point::new(body.width / 2.0, body.height / 2.0);
+
+    // every placed feature's shape
+    let mut corner_cuts: Vec<(BodyCorner, CornerCut)> = Vec::new();
+    let mut edge_profiles: Vec<(BodyEdge, f64, EdgeProfile)> = Vec::new();
+    let mut outline: Option<Contour> = None;
+    for feature in features {
+        let name = feature.shape().name();
+        let Some(shape) = feature.geometry() else {
+            geometry.missing.push(format!("{}x {} (no known shape)", feature.count(), name));
+            continue;
+        };
+        if let FeatureGeometry::PositionedHoles(holes) = shape {
+            geometry.holes.extend(holes);
+            continue;
+        }
+        if let FeatureGeometry::Outline(contour) = shape {
+            outline = Some(contour.translated(center));
+            continue;
+        }
+        let unplaced = feature.count().saturating_sub(feature.placements().len());
+        if unplaced > 0 { geometry.missing.push(format!("{}x {} (not placed)", unplaced, name)); }
+        for placement in feature.placements() {
+            match (&shape, placement) {
+                (FeatureGeometry::Hole(contour), Placement::Point { x, y }) => { geometry.holes.push(contour.translated(Point::new(*x, *y))); }
+                (FeatureGeometry::Corner(cut), Placement::Corner(corner)) => { corner_cuts.push((*corner, *cut)); }
+                (FeatureGeometry::Edge(profile), Placement::Edge { edge, offset }) => { edge_profiles.push((*edge, *offset, profile.clone())); }
+                _ => { geometry.missing.push(format!("the {} at {} (wrong placement)", name, placement.text())); }
+            }
+        }
+    }
+
+    // the outline, with corners cut and profiles cut into the edges
+    geometry.outline = match (outline, body.shape.corners(body.width, body.height)) {
+        (Some(contour), _) => {
+            for (corner, _) in &corner_cuts { geometry.missing.push(format!("the {} corner cut (the outline is a gear)", corner.name())); }
+            for (edge, offset, _) in &edge_profiles { geometry.missing.push(format!("the profile at {}@{} (the outline is a gear)", edge.name(), offset)); }
+            contour
+        }
+        (None, Some(corners)) => { cut_outline(body, &corners, &corner_cuts, &edge_profiles) }
+        (None, None) => { Contour::circle(center, body.width / 2.0) }
+    };
+    if let BodyShape::Annulus { inner_diameter } = body.shape {
+        geometry.holes.insert(0, Contour::circle(center, inner_diameter / 2.0));
+    }
+
+    geometry
+}
+
+/// Returns which of the shape's corners, counted counterclockwise from the bottom left, is the named corner.
+fn corner_index(shape: BodyShape, corner: BodyCorner) -> Option<usize> {
+    match (shape, corner) {
+        (BodyShape::Rectangle, BodyCorner::BottomLeft) | (BodyShape::RightTriangle, BodyCorner::BottomLeft) => { Some(0) }
+        (BodyShape::Rectangle, BodyCorner::BottomRight) | (BodyShape::RightTriangle, BodyCorner::BottomRight) => { Some(1) }
+        (BodyShape::Rectangle, BodyCorner::TopRight) => { Some(2) }
+        (BodyShape::Rectangle, BodyCorner::TopLeft) => { Some(3) }
+        (BodyShape::RightTriangle, BodyCorner::TopLeft) => { Some(2) }
+        _ => { None }
+    }
+}
+
+/// Returns the named edge that runs from one of the shape's corners to the next, if it has a name.
+fn edge_at(shape: BodyShape, index: usize) -> Option<BodyEdge> {
+    match (shape, index) {
+        (BodyShape::Rectangle, 0) | (BodyShape::RightTriangle, 0) => { Some(BodyEdge::Bottom) }
+        (BodyShape::Rectangle, 1) => { Some(BodyEdge::Right) }
+        (BodyShape::Rectangle, 2) => { Some(BodyEdge::Top) }
+        (BodyShape::Rectangle, 3) | (BodyShape::RightTriangle, 2) => { Some(BodyEdge::Left) }
+        // a regular polygon's corners start just right of its flat bottom, so the bottom edge is the last one
+        (BodyShape::RegularPolygon { sides }, index) if index + 1 == sides => { Some(BodyEdge::Bottom) }
+        _ => { None }
+    }
+}
+
+/// Builds a straight sided outline with its corner cuts and edge profiles.
+/// Each edge is built along its own frame, from its left or bottom end and into the body, then joined counterclockwise.
+fn cut_outline(body: &Body, corners: &[(f64, f64)], corner_cuts: &[(BodyCorner, CornerCut)], edge_profiles: &[(BodyEdge, f64, EdgeProfile)]) -> Contour {
+    let count = corners.len();
+    let corners: Vec<Point> = corners.iter().map(|(x, y)| Point::new(*x, *y)).collect();
+
+    // each edge's frame, and whether it runs against the counterclockwise direction
+    let frames: Vec<(Frame, f64, bool)> = (0..count)
+        .map(|index| {
+            let (start, end) = (corners[index], corners[(index + 1) % count]);
+            let reversed = matches!(edge_at(body.shape, index), Some(BodyEdge::Top) | Some(BodyEdge::Left));
+            let (origin, tip) = if reversed { (end, start) } else { (start, end) };
+            let along = tip.sub(origin).unit();
+            let across = if reversed { along.left().scale(-1.0) } else { along.left() };
+            (Frame { origin, along, across }, origin.distance(tip), reversed)
+        })
+        .collect();
+
+    // each edge's profiles in order along it, and how far its far end has stepped into the body
+    let mut profiles: Vec<Vec<(f64, EdgeProfile)>> = vec![Vec::new(); count];
+    for (edge, offset, profile) in edge_profiles {
+        if let Some(index) = (0..count).find(|index| edge_at(body.shape, *index) == Some(*edge)) {
+            profiles[index].push((*offset, profile.clone()));
+        }
+    }
+    for list in &mut profiles { list.sort_by(|(first, _), (second, _)| first.total_cmp(second)); }
+    let levels: Vec<f64> = profiles.iter().map(|list| list.iter().map(|(_, profile)| profile.level).sum()).collect();
+
+    // steps move the corner at the far end of their edge
+    let mut moved = corners.clone();
+    for (index, (frame, _, reversed)) in frames.iter().enumerate() {
+        let far_end = if *reversed { index } else { (index + 1) % count };
+        moved[far_end] = moved[far_end].add(frame.across.scale(levels[index]));
+    }
+
+    // the cut at each corner
+    let mut cuts = vec![CornerCut::Sharp; count];
+    for (corner, cut) in corner_cuts {
+        if let Some(index) = corner_index(body.shape, *corner) { cuts[index] = *cut; }
+    }
+    let directions: Vec<Point> = (0..count).map(|index| corners[(index + 1) % count].sub(corners[index]).unit()).collect();
+    let corner_segments: Vec<Vec<Segment>> = (0..count)
+        .map(|index| cuts[index].segments(moved[index], directions[(index + count - 1) % count], directions[index]))
+        .collect();
+
+    let mut segments = Vec::new();
+    for index in 0..count {
+        let next = (index + 1) % count;
+        let (frame, _, reversed) = &frames[index];
+        // where the edge starts and ends after the corner cuts at either end
+        let start = corner_segments[index].last().map(|segment| segment.end()).unwrap_or(moved[index]);
+        let end = corner_segments[next].first().map(|segment| segment.start()).unwrap_or(moved[next]);
+        let (low, high) = if *reversed { (frame.local(end), frame.local(start)) } else { (frame.local(start), frame.local(end)) };
+
+        // the edge in its own frame, from its left or bottom end
+        let mut local = Vec::new();
+        let mut current = low;
+        let mut level = 0.0;
+        for (offset, profile) in &profiles[index] {
+            let shift = Point::new(*offset, level);
+            let placed: Vec<Segment> = profile.segments.iter().map(|segment| segment.translated(shift)).collect();
+            if let (Some(first), Some(last)) = (placed.first(), placed.last()) {
+                push_line(&mut local, current, first.start());
+                local.extend(placed.iter().copied());
+                current = last.end();
+            }
+            level += profile.level;
+        }
+        push_line(&mut local, current, high);
+
+        let mut edge: Vec<Segment> = local.iter().map(|segment| segment.mapped(frame)).collect();
+        if *reversed { edge = edge.iter().rev().map(|segment| segment.reversed()).collect(); }
+        segments.extend(edge);
+        segments.extend(corner_segments[next].iter().copied());
+    }
+    Contour::new(segments)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use crate::export::csv::body_from_csv;
+    use crate::geometry::{arc_through, Contour, FeatureGeometry, Point, Segment};
+    use crate::shapes::{formulas, Body, CapsularHole, Feature};
+
+    /// Checks that two lengths agree to well within what any summary shows.
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+    }
+
+    /// Imports a 100 by 50 plate with the given feature rows.
+    fn plate(rows: &str) -> Body {
+        body_from_csv(&format!("body,name=plate; width=100; height=50; quantity=1,1\n{}", rows)).unwrap_or_else(|errors| panic!("unexpected errors: {:?}", errors))
+    }
+
+    /// Checks that the outline has a segment running between the two points, in either direction.
+    fn assert_has_segment(contour: &Contour, a: Point, b: Point) {
+        let found = contour.segments.iter().any(|segment| {
+            let (start, end) = (segment.start(), segment.end());
+            (start.distance(a) < 1e-9 && end.distance(b) < 1e-9) || (start.distance(b) < 1e-9 && end.distance(a) < 1e-9)
+        });
+        assert!(found, "no segment from ({}, {}) to ({}, {})", a.x, a.y, b.x, b.y);
+    }
+
+    #[test]
+    fn circle_offset_grows_and_shrinks_with_the_radius() {
+        let circle = Contour::circle(Point::new(5.0, 5.0), 10.0);
+        assert_close(circle.offset_length(2.0).unwrap(), 2.0 * PI * 12.0);
+        assert_close(circle.offset_length(-2.0).unwrap(), 2.0 * PI * 8.0);
+        assert!(circle.offset_length(-12.0).is_none());
+    }
+
+    #[test]
+    fn rectangle_offset_rounds_outside_corners_and_trims_inside_ones() {
+        let rectangle = Contour::polygon(&[Point::new(0.0, 0.0), Point::new(100.0, 0.0), Point::new(100.0, 50.0), Point::new(0.0, 50.0)]);
+        assert_close(rectangle.offset_length(0.0).unwrap(), 300.0);
+        assert_close(rectangle.offset_length(1.0).unwrap(), 300.0 + (2.0 * PI));
+        assert_close(rectangle.offset_length(-1.0).unwrap(), 300.0 - 8.0);
+        // the same rectangle run clockwise grows the same way
+        let clockwise = Contour::new(rectangle.segments.iter().rev().map(|segment| segment.reversed()).collect());
+        assert_close(clockwise.offset_length(1.0).unwrap(), 300.0 + (2.0 * PI));
+        assert!(rectangle.offset_length(-30.0).is_none());
+    }
+
+    #[test]
+    fn arc_through_three_points_on_a_circle() {
+        let counterclockwise = arc_through(Point::new(10.0, 0.0), Point::new(0.0, 10.0), Point::new(-10.0, 0.0));
+        let clockwise = arc_through(Point::new(-10.0, 0.0), Point::new(0.0, 10.0), Point::new(10.0, 0.0));
+        for arc in [counterclockwise, clockwise] {
+            let Segment::Arc { center, radius, .. } = arc else { panic!("expected an arc") };
+            assert_close(center.distance(Point::new(0.0, 0.0)), 0.0);
+            assert_close(radius, 10.0);
+            assert_close(arc.length(), 10.0 * PI);
+        }
+        let Segment::Arc { sweep, .. } = clockwise else { panic!("expected an arc") };
+        assert!(sweep < 0.0);
+        assert_close(clockwise.end().distance(Point::new(10.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn ellipse_perimeter_limits() {
+        assert_close(formulas::ellipse_perimeter(7.0, 7.0), 2.0 * PI * 7.0);
+        assert_close(formulas::ellipse_perimeter(7.0, 0.0), 28.0);
+        assert_close(formulas::ellipse_perimeter(0.0, 7.0), 28.0);
+    }
+
+    #[test]
+    fn capsule_outline_matches_the_capsular_hole_formula() {
+        let hole = CapsularHole::new(10.0, 30.0, 1);
+        let Some(FeatureGeometry::Hole(contour)) = hole.geometry() else { panic!("expected a hole") };
+        assert_close(contour.length(), hole.value());
+        assert_close(contour.length(), (PI * 10.0) + (2.0 * 20.0));
+    }
+
+    #[test]
+    fn edge_features_are_cut_into_their_edge_at_their_offset() {
+        let body = plate("edge slot,width=10; depth=20; full radius end=1; placement=bottom@30,1\nnotch,width=10; depth=5; wall angle=0; bottom style=0; relief radius=0; relief style=0; placement=left@20,1\n");
+        let geometry = body.geometry();
+        assert!(geometry.missing.is_empty());
+        // the slot's walls rise from the bottom edge at 30 and 40, which runs straight up to the slot
+        assert_has_segment(&geometry.outline, Point::new(0.0, 0.0), Point::new(30.0, 0.0));
+        assert_has_segment(&geometry.outline, Point::new(30.0, 0.0), Point::new(30.0, 15.0));
+        assert_has_segment(&geometry.outline, Point::new(40.0, 15.0), Point::new(40.0, 0.0));
+        // the notch's flat bottom sits 5 into the left edge between 20 and 30
+        assert_has_segment(&geometry.outline, Point::new(5.0, 20.0), Point::new(5.0, 30.0));
+    }
+
+    #[test]
+    fn drawn_features_match_their_formulas() {
+        for row in [
+            "notch,width=10; depth=5; wall angle=0; bottom style=0; relief radius=0; relief style=0; placement=top@20,1",
+            "notch,width=10; depth=5; wall angle=15; bottom style=1; relief radius=0; relief style=0; placement=top@20,1",
+            "edge slot,width=10; depth=20; full radius end=1; placement=bottom@30,1",
+            "dovetail,depth=5; opening width=10; angle=60; placement=bottom@30,1",
+            "t-slot,neck width=6; neck depth=4; head width=14; head depth=5; placement=top@40,1",
+            "ellipse,width=20; height=10; placement=right@10,1",
+            "circular hole,\"diameter=10; placement=50,25\",1",
+            "elliptical hole,\"width=20; height=10; placement=50,25\",1",
+            "keyhole,\"large diameter=10; slot width=4; slot length=12; placement=50,25\",1",
+            "d hole,\"diameter=10; across flats=8; flats=2; placement=50,25\",1",
+        ] {
+            let body = plate(&format!("{}\n", row));
+            let geometry = body.geometry();
+            assert!(geometry.missing.is_empty(), "{} was not drawn", row);
+            // each feature changes the plate's 300 by its formula, to within 0.01% for curves drawn as fine lines
+            let (formula, drawn) = (body.perimeter() - 300.0, geometry.perimeter() - 300.0);
+            assert!((formula - drawn).abs() < 1e-9_f64.max(drawn.abs() * 1e-4), "{}: formula {}, drawn {}", row, formula, drawn);
+        }
+    }
+
+    #[test]
+    fn regular_polygons_take_edge_features_on_their_bottom_edge() {
+        let text = concat!(
+            "body,name=hexagon; shape=regular polygon; width=100; height=100; sides=6; quantity=1,1\n",
+            "notch,width=10; depth=5; wall angle=0; bottom style=0; relief radius=0; relief style=0; placement=bottom@20,1\n",
+        );
+        let body = body_from_csv(text).unwrap_or_else(|errors| panic!("unexpected errors: {:?}", errors));
+        let geometry = body.geometry();
+        assert!(geometry.missing.is_empty(), "{:?}", geometry.missing);
+        // the flat bottom runs from (25, 6.699) to (75, 6.699), so the notch's bottom sits 5 above it from 45 to 55
+        let bottom = 50.0 - (50.0 * (PI / 6.0).cos());
+        assert_has_segment(&geometry.outline, Point::new(45.0, bottom + 5.0), Point::new(55.0, bottom + 5.0));
+        assert_close(geometry.perimeter(), body.perimeter());
+        assert!(body.interferences().is_empty(), "{:?}", body.interferences());
+    }
+
+    #[test]
+    fn verify_finds_no_mismatch_on_notches_slots_and_ellipses() {
+        let body = plate(concat!(
+            "notch,width=10; depth=5; wall angle=0; bottom style=1; relief radius=0; relief style=0; placement=top@20,1\n",
+            "edge slot,width=10; depth=20; full radius end=1; placement=bottom@30,1\n",
+            "ellipse,width=20; height=10; placement=right@10,1\n",
+        ));
+        let before = body.perimeter();
+        let verification = body.verify();
+        let checks = verification.iter().filter(|line| line.contains(": ") && !line.contains("perimeter:")).collect::<Vec<&String>>();
+        assert_eq!(checks.len(), 5, "{:?}", verification);
+        assert!(checks.iter().all(|line| line.starts_with("ok: ")), "{:?}", verification);
+        // verifying only reads the body
+        assert_eq!(body.features.len(), 3);
+        assert_close(body.perimeter(), before);
+    }
+}
@@ -10,6 +10,8 @@ use crate::app::App;
 mod shapes;
 mod app;
 mod ui;
+mod export;
+mod geometry;
 
 fn main() -> Result<()> {
     // setup terminal
@@ -1,18 +1,26 @@
 use std::any::Any;
 use std::cmp::PartialEq;
-use ratatui::text::Line;
-use genpdf::{fonts, SimplePageDecorator};
-use genpdf::elements::Paragraph as PdfParagraph;
+use std::f64::consts::PI;
+use crate::geometry::{self, BodyGeometry, Contour, CornerCut, EdgeProfile, FeatureGeometry, Point, Segment};
 
 /// The list of possible features.
 #[derive(Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum Features {
     CircularHoleFeature,
     CapsularHoleFeature,
     RectangularHoleFeature,
+    PolygonHoleFeature,
+    KeyholeFeature,
+    DHoleFeature,
+    EllipticalHoleFeature,
+    SegmentHoleFeature,
+    PolarHolePatternFeature,
+    GridHolePatternFeature,
 
     FilletFeature,
     ChamferFeature,
+    InternalCornerFeature,
     SlopeFeature,
     CliffFeature,
 
@@ -20,33 +28,131 @@ pub enum Features {
     SawtoothFeature,
     ClawFeature,
     CompositeSlopeFeature,
+    EdgeSlotFeature,
+    DovetailFeature,
+    TSlotFeature,
+    FreeFormEdgeFeature,
+    PeriodicEdgeFeature,
     
     ArcFeature,
     EllipseFeature,
+    EllipticalArcFeature,
+    GearFeature,
 
     OtherFeatureFeature,
 }
 impl Features {
+    /// Returns how the feature is placed on the body, or nothing if it cannot be placed.
+    pub fn placement_kind(&self) -> Option<PlacementKind> {
+        match self {
+            Features::CircularHoleFeature
+            | Features::CapsularHoleFeature
+            | Features::RectangularHoleFeature
+            | Features::PolygonHoleFeature
+            | Features::KeyholeFeature
+            | Features::DHoleFeature
+            | Features::EllipticalHoleFeature
+            | Features::SegmentHoleFeature
+            | Features::GearFeature => { Some(PlacementKind::Point) }
+            Features::FilletFeature | Features::ChamferFeature => { Some(PlacementKind::Corner) }
+            Features::SlopeFeature
+            | Features::CliffFeature
+            | Features::NotchFeature
+            | Features::SawtoothFeature
+            | Features::ClawFeature
+            | Features::CompositeSlopeFeature
+            | Features::EdgeSlotFeature
+            | Features::DovetailFeature
+            | Features::TSlotFeature
+            | Features::FreeFormEdgeFeature
+            | Features::PeriodicEdgeFeature
+            | Features::ArcFeature
+            | Features::EllipseFeature
+            | Features::EllipticalArcFeature => { Some(PlacementKind::Edge) }
+            _ => { None }
+        }
+    }
+
+    /// Returns whether the feature modifies one of the body's outside corners.
+    pub fn is_corner(&self) -> bool {
+        matches!(self, Features::FilletFeature | Features::ChamferFeature | Features::SlopeFeature | Features::CliffFeature)
+    }
+
     /// Returns the name of the feature.
     pub fn name(&self) -> String {
         match self {
             Features::CircularHoleFeature => { "circular hole".to_string() }
             Features::CapsularHoleFeature => { "capsular hole".to_string() }
             Features::RectangularHoleFeature => { "rectangular hole".to_string() }
+            Features::PolygonHoleFeature => { "polygon hole".to_string() }
+            Features::KeyholeFeature => { "keyhole".to_string() }
+            Features::DHoleFeature => { "d hole".to_string() }
+            Features::EllipticalHoleFeature => { "elliptical hole".to_string() }
+            Features::SegmentHoleFeature => { "segment hole".to_string() }
+            Features::PolarHolePatternFeature => { "polar hole pattern".to_string() }
+            Features::GridHolePatternFeature => { "grid hole pattern".to_string() }
             Features::FilletFeature => { "fillet".to_string() }
             Features::ChamferFeature => { "chamfer".to_string() }
+            Features::InternalCornerFeature => { "internal corner".to_string() }
             Features::SlopeFeature => { "slope".to_string() }
             Features::CliffFeature => { "cliff".to_string() }
             Features::NotchFeature => { "notch".to_string() }
             Features::SawtoothFeature => { "sawtooth".to_string() }
             Features::ClawFeature => { "claw".to_string() }
             Features::CompositeSlopeFeature => { "composite slope".to_string() }
+            Features::EdgeSlotFeature => { "edge slot".to_string() }
+            Features::DovetailFeature => { "dovetail".to_string() }
+            Features::TSlotFeature => { "t-slot".to_string() }
+            Features::FreeFormEdgeFeature => { "free-form edge".to_string() }
+            Features::PeriodicEdgeFeature => { "periodic edge".to_string() }
             Features::ArcFeature => { "arc".to_string() }
             Features::EllipseFeature => { "ellipse".to_string() }
+            Features::EllipticalArcFeature => { "elliptical arc".to_string() }
+            Features::GearFeature => { "gear".to_string() }
             Features::OtherFeatureFeature => { "other feature".to_string() }
         }
     }
 
+    /// Returns every type of feature.
+    pub fn all() -> Vec<Features> {
+        vec![
+            Features::CircularHoleFeature,
+            Features::CapsularHoleFeature,
+            Features::RectangularHoleFeature,
+            Features::PolygonHoleFeature,
+            Features::KeyholeFeature,
+            Features::DHoleFeature,
+            Features::EllipticalHoleFeature,
+            Features::SegmentHoleFeature,
+            Features::PolarHolePatternFeature,
+            Features::GridHolePatternFeature,
+            Features::FilletFeature,
+            Features::ChamferFeature,
+            Features::InternalCornerFeature,
+            Features::SlopeFeature,
+            Features::CliffFeature,
+            Features::NotchFeature,
+            Features::SawtoothFeature,
+            Features::ClawFeature,
+            Features::CompositeSlopeFeature,
+            Features::EdgeSlotFeature,
+            Features::DovetailFeature,
+            Features::TSlotFeature,
+            Features::FreeFormEdgeFeature,
+            Features::PeriodicEdgeFeature,
+            Features::ArcFeature,
+            Features::EllipseFeature,
+            Features::EllipticalArcFeature,
+            Features::GearFeature,
+            Features::OtherFeatureFeature,
+        ]
+    }
+
+    /// Returns the type of feature with the given name.
+    pub fn from_name(name: &str) -> Option<Features> {
+        Features::all().into_iter().find(|feature| feature.name() == name.trim().to_lowercase())
+    }
+
     /// Returns the list of steps needed to add a feature for a given type.
     pub fn steps(&self) -> Vec<FeatureAdditionStep> {
         let mut steps = Vec::new();
@@ -66,16 +172,79 @@ impl Features {
             Features::RectangularHoleFeature => {
                 steps.push(FeatureAdditionStep::new("width".to_string()));
                 steps.push(FeatureAdditionStep::new("height".to_string()));
+                steps.push(FeatureAdditionStep::new("corner radius".to_string()));
+                steps.push(FeatureAdditionStep::new("corner style".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::PolygonHoleFeature => {
+                steps.push(FeatureAdditionStep::new("sides".to_string()));
+                steps.push(FeatureAdditionStep::new("size".to_string()));
+                steps.push(FeatureAdditionStep::new("size type".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::KeyholeFeature => {
+                steps.push(FeatureAdditionStep::new("large diameter".to_string()));
+                steps.push(FeatureAdditionStep::new("slot width".to_string()));
+                steps.push(FeatureAdditionStep::new("slot length".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::DHoleFeature => {
+                steps.push(FeatureAdditionStep::new("diameter".to_string()));
+                steps.push(FeatureAdditionStep::new("across flats".to_string()));
+                steps.push(FeatureAdditionStep::new("flats".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::EllipticalHoleFeature => {
+                steps.push(FeatureAdditionStep::new("width".to_string()));
+                steps.push(FeatureAdditionStep::new("height".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::SegmentHoleFeature => {
+                steps.push(FeatureAdditionStep::new("width".to_string()));
+                steps.push(FeatureAdditionStep::new("height".to_string()));
+                steps.push(FeatureAdditionStep::new("segment height".to_string()));
                 steps.push(FeatureAdditionStep::new("count".to_string()));
             }
 
+            Features::PolarHolePatternFeature => {
+                steps.push(FeatureAdditionStep::new("hole diameter".to_string()));
+                steps.push(FeatureAdditionStep::new("holes".to_string()));
+                steps.push(FeatureAdditionStep::new("pitch circle diameter".to_string()));
+                steps.push(FeatureAdditionStep::new("start angle".to_string()));
+                steps.push(FeatureAdditionStep::new("center x".to_string()));
+                steps.push(FeatureAdditionStep::new("center y".to_string()));
+            }
+
+            Features::GridHolePatternFeature => {
+                steps.push(FeatureAdditionStep::new("hole diameter".to_string()));
+                steps.push(FeatureAdditionStep::new("columns".to_string()));
+                steps.push(FeatureAdditionStep::new("rows".to_string()));
+                steps.push(FeatureAdditionStep::new("column pitch".to_string()));
+                steps.push(FeatureAdditionStep::new("row pitch".to_string()));
+                steps.push(FeatureAdditionStep::new("origin x".to_string()));
+                steps.push(FeatureAdditionStep::new("origin y".to_string()));
+            }
+
             Features::FilletFeature => {
                 steps.push(FeatureAdditionStep::new("radius".to_string()));
                 steps.push(FeatureAdditionStep::new("count".to_string()));
             }
 
             Features::ChamferFeature => {
-                steps.push(FeatureAdditionStep::new("size".to_string()));
+                steps.push(FeatureAdditionStep::new("leg".to_string()));
+                steps.push(FeatureAdditionStep::new("second size".to_string()));
+                steps.push(FeatureAdditionStep::new("second size type".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::InternalCornerFeature => {
+                steps.push(FeatureAdditionStep::new("radius".to_string()));
+                steps.push(FeatureAdditionStep::new("relief style".to_string()));
                 steps.push(FeatureAdditionStep::new("count".to_string()));
             }
 
@@ -92,7 +261,12 @@ impl Features {
             }
 
             Features::NotchFeature => {
+                steps.push(FeatureAdditionStep::new("width".to_string()));
                 steps.push(FeatureAdditionStep::new("depth".to_string()));
+                steps.push(FeatureAdditionStep::signed("wall angle".to_string()));
+                steps.push(FeatureAdditionStep::new("bottom style".to_string()));
+                steps.push(FeatureAdditionStep::new("relief radius".to_string()));
+                steps.push(FeatureAdditionStep::new("relief style".to_string()));
                 steps.push(FeatureAdditionStep::new("count".to_string()));
             }
 
@@ -117,6 +291,42 @@ impl Features {
                 steps.push(FeatureAdditionStep::new("count".to_string()));
             }
 
+            Features::EdgeSlotFeature => {
+                steps.push(FeatureAdditionStep::new("width".to_string()));
+                steps.push(FeatureAdditionStep::new("depth".to_string()));
+                steps.push(FeatureAdditionStep::new("full radius end".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::DovetailFeature => {
+                steps.push(FeatureAdditionStep::new("depth".to_string()));
+                steps.push(FeatureAdditionStep::new("opening width".to_string()));
+                steps.push(FeatureAdditionStep::new("angle".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::TSlotFeature => {
+                steps.push(FeatureAdditionStep::new("neck width".to_string()));
+                steps.push(FeatureAdditionStep::new("neck depth".to_string()));
+                steps.push(FeatureAdditionStep::new("head width".to_string()));
+                steps.push(FeatureAdditionStep::new("head depth".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::FreeFormEdgeFeature => {
+                steps.push(FeatureAdditionStep::text("points".to_string()));
+                steps.push(FeatureAdditionStep::new("smoothing".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::PeriodicEdgeFeature => {
+                steps.push(FeatureAdditionStep::new("profile".to_string()));
+                steps.push(FeatureAdditionStep::new("edge length".to_string()));
+                steps.push(FeatureAdditionStep::new("pitch".to_string()));
+                steps.push(FeatureAdditionStep::new("amplitude".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
             Features::ArcFeature => {
                 steps.push(FeatureAdditionStep::new("radius".to_string()));
                 steps.push(FeatureAdditionStep::new("height".to_string()));
@@ -129,11 +339,32 @@ impl Features {
                 steps.push(FeatureAdditionStep::new("count".to_string()));
             }
 
+            Features::EllipticalArcFeature => {
+                steps.push(FeatureAdditionStep::new("width".to_string()));
+                steps.push(FeatureAdditionStep::new("height".to_string()));
+                steps.push(FeatureAdditionStep::signed("start angle".to_string()));
+                steps.push(FeatureAdditionStep::signed("end angle".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
+            Features::GearFeature => {
+                steps.push(FeatureAdditionStep::new("module or pitch".to_string()));
+                steps.push(FeatureAdditionStep::new("pitch type".to_string()));
+                steps.push(FeatureAdditionStep::new("teeth".to_string()));
+                steps.push(FeatureAdditionStep::new("pressure angle".to_string()));
+                steps.push(FeatureAdditionStep::new("internal".to_string()));
+                steps.push(FeatureAdditionStep::new("count".to_string()));
+            }
+
             Features::OtherFeatureFeature => {
                 steps.push(FeatureAdditionStep::new("perimeter modification".to_string()));
                 steps.push(FeatureAdditionStep::new("count".to_string()));
             }
         }
+
+        // every placeable feature can optionally be placed after its count
+        if self.placement_kind().is_some() { steps.push(FeatureAdditionStep::text("placement".to_string())); }
+
         steps
     }
 
@@ -162,13 +393,21 @@ pub trait Feature {
 
     /// Returns a collection of summary information
     fn summarize(&self) -> Vec<String> {
+        if self.single_line() {
+            let placements = self.summarize_placements().map(|placements| format!(", {}", placements)).unwrap_or_default();
+            return vec![format!("{}: {}{}, {}", self.summarize_feature(), self.summarize_dimensions().concat(), placements, self.summarize_modification())];
+        }
         let mut summary = Vec::new();
         summary.push(self.summarize_feature());
         summary.extend(self.summarize_dimensions());
+        summary.extend(self.summarize_placements());
         summary.push(self.summarize_modification());
         summary
     }
 
+    /// Returns whether the summary fits on one line instead of a line for each part.
+    fn single_line(&self) -> bool { false }
+
     /// Returns a standardized summary of the feature.
     fn summarize_feature(&self) -> String {
         format!("{}x {}", self.count(), self.shape().name())
@@ -177,6 +416,28 @@ pub trait Feature {
     /// Returns a standardized dimension list of the feature.
     fn summarize_dimensions(&self) -> Vec<String>;
 
+    /// Returns where each instance of the feature sits on the body, if it has been placed.
+    /// Only features wrapped in Placed have placements.
+    fn placements(&self) -> &[Placement] { &[] }
+
+    /// Returns the size of the area one instance takes up around its placement, if it is known.
+    /// This is the width and height around a hole's center, or the length along the edge and the depth into the body for edge features.
+    fn extent(&self) -> Option<(f64, f64)> { None }
+
+    /// Returns the outline of one instance, if its shape is known.
+    /// Holes are drawn around their center and edge profiles run along the edge from their offset and into the body.
+    fn geometry(&self) -> Option<FeatureGeometry> { None }
+
+    /// Returns a standardized placement list of the feature, if any instance has been placed.
+    fn summarize_placements(&self) -> Option<String> {
+        if self.placements().is_empty() { return None; }
+        Some(format!("placement: {}", Placement::list_text(self.placements())))
+    }
+
+    /// Returns the feature's parameters by step name, written the way they would be entered in its addition path.
+    /// The count is not included.
+    fn parameters(&self) -> Vec<(String, String)>;
+
     /// Returns a standardized modification summary of the feature.
     fn summarize_modification(&self) -> String {
         format!("modification: {}", &format!("{:.3}", self.value()))
@@ -186,6 +447,10 @@ pub trait Feature {
     /// The perimeter modification is the difference in the body's overall perimeter that this feature will create.
     fn value(&self) -> f64;
 
+    /// Returns how many separate cuts must be pierced to make the feature.
+    /// Only holes need their own pierce, so this is zero for everything else.
+    fn pierces(&self) -> usize { 0 }
+
     fn print_height(&self) -> u16 {
         self.summarize().len() as u16
     }
@@ -202,29 +467,246 @@ pub struct FeatureAdditionPath {
     current_step: usize,
     /// The list of steps required to create the feature.
     steps: Vec<FeatureAdditionStep>,
+    /// Why the entered values could not make a valid feature, if they could not.
+    pub error: Option<String>,
 }
 impl FeatureAdditionPath {
     /// Creates a new path for adding a feature.
-    pub fn new(feature: Features, steps: Vec<FeatureAdditionStep>) -> Self { Self { feature, current_step: 0, steps } }
+    pub fn new(feature: Features, steps: Vec<FeatureAdditionStep>) -> Self { Self { feature, current_step: 0, steps, error: None } }
 
     /// Returns the current step's field.
     pub fn current_step_value(&self) -> String { self.steps[self.current_step].field.clone() }
 
+    /// Returns what kind of input the current step takes.
+    pub fn current_step_kind(&self) -> StepKind { self.steps[self.current_step].kind }
+
     /// Returns the current step's field.
     pub fn current_step_value_input(&self) -> String { self.steps[self.current_step].value_input.clone() }
 
+    /// Returns the field of every step.
+    pub fn fields(&self) -> Vec<String> { self.steps.iter().map(|step| step.field.clone()).collect() }
+
     /// Updates the current step's value input.
     pub fn update_current_step_value_input(&mut self, new_value_input: String) { self.steps[self.current_step].update_value_input(new_value_input); }
 
     /// Finishes the current step and returns the completed feature if the last step was completed.
+    /// If the finished values are invalid, the error is recorded and the path starts over with its inputs kept.
     pub fn finish_current_step(&mut self) -> Option<Box<dyn Feature>> {
         self.steps[self.current_step].finish();
         if self.current_step < self.steps.len() - 1 {
             self.current_step += 1;
             None
         }
+        else if let Err(error) = self.validate() {
+            self.reject(error);
+            None
+        }
         else {
-            Some(self.create_feature())
+            self.error = None;
+            let feature = self.create_feature();
+            match self.feature.placement_kind() {
+                Some(kind) => {
+                    let placements = Placement::parse_list(kind, &self.steps[self.steps.len() - 1].value_input).unwrap_or_default();
+                    Some(Box::new(Placed::new(feature, placements)))
+                }
+                None => { Some(feature) }
+            }
+        }
+    }
+
+    /// Records why the feature could not be added and starts the path over with its inputs kept.
+    pub fn reject(&mut self, error: String) {
+        self.error = Some(error);
+        self.current_step = 0;
+    }
+
+    /// Checks that the finished values make a valid feature.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(kind) = self.feature.placement_kind() {
+            let placements = Placement::parse_list(kind, &self.steps[self.steps.len() - 1].value_input)?;
+            let count = self.steps.iter().find(|step| step.field == "count").map(|step| step.value).unwrap_or(1.0);
+            if placements.len() as f64 > count { return Err(format!("there are more placements ({}) than features ({})", placements.len(), count)); }
+        }
+
+        match self.feature {
+            Features::NotchFeature => {
+                let width = self.steps[0].value;
+                let depth = self.steps[1].value;
+                let wall_angle = self.steps[2].value;
+                let bottom = NotchBottom::from_value(self.steps[3].value);
+                let relief_radius = self.steps[4].value;
+                let relief = CornerRelief::from_value(self.steps[5].value);
+                if wall_angle.abs() >= 90.0 { return Err("the wall angle must be between -90 and 90 degrees".to_string()); }
+                match bottom {
+                    NotchBottom::Flat => {
+                        let bottom_width = formulas::notch_bottom_width(width, depth, wall_angle);
+                        if bottom_width < 0.0 { return Err("the walls cross before reaching the depth".to_string()); }
+                        if relief_radius <= 0.0 { return Ok(()); }
+                        match relief {
+                            CornerRelief::Radius => {
+                                let tangent_length = formulas::notch_corner_tangent_length(relief_radius, wall_angle);
+                                if tangent_length > bottom_width / 2.0 || tangent_length > depth / wall_angle.to_radians().cos() {
+                                    return Err("the relief radius does not fit in the notch".to_string());
+                                }
+                                Ok(())
+                            }
+                            _ if wall_angle != 0.0 => { Err("dog-bone and t-bone reliefs need straight walls".to_string()) }
+                            _ if 2.0 * relief.wall_length(relief_radius) > width || relief.wall_length(relief_radius) > depth => { Err("the reliefs do not fit in the notch".to_string()) }
+                            _ => { Ok(()) }
+                        }
+                    }
+                    NotchBottom::FullRound => {
+                        if relief_radius > 0.0 { return Err("a full round bottom cannot also have corner reliefs".to_string()); }
+                        let radius = formulas::full_round_notch_radius(width, depth, wall_angle);
+                        if radius <= 0.0 || formulas::full_round_notch_wall_length(width, depth, wall_angle) < 0.0 {
+                            return Err("the notch is too shallow for a full round bottom at this width".to_string());
+                        }
+                        Ok(())
+                    }
+                }
+            }
+
+            Features::ChamferFeature => {
+                let second_size = self.steps[1].value;
+                if self.steps[2].value as i32 == 1 && (second_size <= 0.0 || second_size >= 90.0) { return Err("the angle must be between 0 and 90 degrees".to_string()); }
+                if second_size < 0.0 { return Err("the second leg cannot be negative".to_string()); }
+                Ok(())
+            }
+
+            Features::RectangularHoleFeature => {
+                let width = self.steps[0].value;
+                let height = self.steps[1].value;
+                let corner_radius = self.steps[2].value;
+                let corner_style = CornerRelief::from_value(self.steps[3].value);
+                if corner_radius < 0.0 { return Err("the corner radius cannot be negative".to_string()); }
+                match corner_style {
+                    CornerRelief::Radius if corner_radius > width.min(height) / 2.0 => { Err("the corner radius can be at most half of the smaller side".to_string()) }
+                    CornerRelief::DogBone if 2.0 * corner_style.wall_length(corner_radius) > width.min(height) => { Err("the dog-bones at each end of a side overlap".to_string()) }
+                    CornerRelief::TBone if 2.0 * corner_style.wall_length(corner_radius) > width.max(height) => { Err("the t-bones at each end of a side overlap".to_string()) }
+                    _ => { Ok(()) }
+                }
+            }
+
+            Features::PolygonHoleFeature => {
+                let sides = self.steps[0].value;
+                if sides < 3.0 || sides.fract() != 0.0 { return Err("a polygon needs a whole number of at least 3 sides".to_string()); }
+                Ok(())
+            }
+
+            Features::KeyholeFeature => {
+                let large_diameter = self.steps[0].value;
+                let slot_width = self.steps[1].value;
+                let slot_length = self.steps[2].value;
+                if slot_width <= 0.0 || slot_width >= large_diameter { return Err("the slot must be narrower than the large circle".to_string()); }
+                let join = ((large_diameter / 2.0).powi(2) - (slot_width / 2.0).powi(2)).sqrt();
+                if slot_length - (slot_width / 2.0) <= join { return Err("the slot must reach past the large circle".to_string()); }
+                Ok(())
+            }
+
+            Features::DHoleFeature => {
+                let diameter = self.steps[0].value;
+                let across_flats = self.steps[1].value;
+                let flats = self.steps[2].value;
+                if flats != 1.0 && flats != 2.0 { return Err("a d hole has 1 or 2 flats".to_string()); }
+                if across_flats <= 0.0 || across_flats >= diameter { return Err("the flats must be inside the diameter".to_string()); }
+                Ok(())
+            }
+
+            Features::PolarHolePatternFeature => {
+                let diameter = self.steps[0].value;
+                let holes = self.steps[1].value;
+                let pitch_circle_diameter = self.steps[2].value;
+                if holes < 1.0 || holes.fract() != 0.0 { return Err("a pattern needs a whole number of at least 1 hole".to_string()); }
+                if holes > 1.0 && pitch_circle_diameter * (PI / holes).sin() <= diameter { return Err("the holes overlap each other on the pitch circle".to_string()); }
+                Ok(())
+            }
+
+            Features::GridHolePatternFeature => {
+                let diameter = self.steps[0].value;
+                let columns = self.steps[1].value;
+                let rows = self.steps[2].value;
+                let column_pitch = self.steps[3].value;
+                let row_pitch = self.steps[4].value;
+                if columns < 1.0 || columns.fract() != 0.0 || rows < 1.0 || rows.fract() != 0.0 { return Err("a grid needs a whole number of at least 1 column and 1 row".to_string()); }
+                if columns > 1.0 && column_pitch <= diameter { return Err("the holes overlap each other along the rows".to_string()); }
+                if rows > 1.0 && row_pitch <= diameter { return Err("the holes overlap each other along the columns".to_string()); }
+                Ok(())
+            }
+
+            Features::EdgeSlotFeature => {
+                let width = self.steps[0].value;
+                let depth = self.steps[1].value;
+                let full_radius = self.steps[2].value != 0.0;
+                if full_radius && depth < width / 2.0 { return Err("a full radius end needs a depth of at least half the width".to_string()); }
+                Ok(())
+            }
+
+            Features::FreeFormEdgeFeature => {
+                let points = FreeFormEdge::parse_points(&self.steps[0].value_input)?;
+                if points.len() < 2 { return Err("a free-form edge needs at least 2 points".to_string()); }
+                if formulas::chord_length(&points) <= 0.0 { return Err("the first and last points must be apart".to_string()); }
+                Ok(())
+            }
+
+            Features::GearFeature => {
+                let module = Gear::module_from(self.steps[0].value, self.steps[1].value != 0.0);
+                let teeth = self.steps[2].value;
+                let pressure_angle = self.steps[3].value;
+                let internal = self.steps[4].value != 0.0;
+                let count = self.steps[5].value;
+                if !module.is_finite() || module <= 0.0 { return Err("the module or pitch must be more than 0".to_string()); }
+                if teeth < 6.0 || teeth.fract() != 0.0 { return Err("a gear needs a whole number of at least 6 teeth".to_string()); }
+                if pressure_angle <= 0.0 || pressure_angle >= 45.0 { return Err("the pressure angle must be between 0 and 45 degrees".to_string()); }
+                if !internal && count != 1.0 { return Err("an external gear is the body's outline, so its count must be 1".to_string()); }
+                let radii = formulas::gear_radii(module, teeth as usize, pressure_angle, internal);
+                if radii.inner <= 0.0 { return Err("the gear has too few teeth for its tooth depth".to_string()); }
+                if formulas::gear_half_angle(module, teeth as usize, pressure_angle, radii.outer) <= 0.0 { return Err("the teeth come to a point before reaching full depth".to_string()); }
+                if formulas::gear_half_angle(module, teeth as usize, pressure_angle, radii.inner) >= PI / teeth { return Err("the teeth run into each other at the inner circle".to_string()); }
+                Ok(())
+            }
+
+            Features::PeriodicEdgeFeature => {
+                let edge_length = self.steps[1].value;
+                let pitch = self.steps[2].value;
+                let amplitude = self.steps[3].value;
+                if pitch <= 0.0 || edge_length <= 0.0 { return Err("the edge length and pitch must be more than 0".to_string()); }
+                let repeats = edge_length / pitch;
+                if (repeats - repeats.round()).abs() > 1e-6 || repeats.round() < 1.0 { return Err("the edge length must be a whole number of pitches".to_string()); }
+                let profile = PeriodicProfile::from_value(self.steps[0].value);
+                if matches!(profile, PeriodicProfile::Scallops) && 2.0 * amplitude > pitch / 2.0 {
+                    return Err("scallops can be at most half circles, so the amplitude can be at most a quarter of the pitch".to_string());
+                }
+                Ok(())
+            }
+
+            Features::EllipticalArcFeature => {
+                let start_angle = self.steps[2].value;
+                let end_angle = self.steps[3].value;
+                if end_angle <= start_angle || end_angle - start_angle > 360.0 { return Err("the end angle must be after the start angle and within one turn of it".to_string()); }
+                Ok(())
+            }
+
+            Features::SegmentHoleFeature => {
+                let height = self.steps[1].value;
+                let segment_height = self.steps[2].value;
+                if segment_height <= 0.0 || segment_height >= height { return Err("the segment height must be more than 0 and less than the height".to_string()); }
+                Ok(())
+            }
+
+            Features::DovetailFeature => {
+                let angle = self.steps[2].value;
+                if angle <= 0.0 || angle > 90.0 { return Err("the angle must be more than 0 and at most 90 degrees".to_string()); }
+                Ok(())
+            }
+
+            Features::TSlotFeature => {
+                let neck_width = self.steps[0].value;
+                let head_width = self.steps[2].value;
+                if head_width <= neck_width { return Err("the head must be wider than the neck".to_string()); }
+                Ok(())
+            }
+
+            _ => { Ok(()) }
         }
     }
 
@@ -245,10 +727,70 @@ impl FeatureAdditionPath {
             }
 
             Features::RectangularHoleFeature => {
+                let width = self.steps[0].value;
+                let height = self.steps[1].value;
+                let corner_radius = self.steps[2].value;
+                let corner_style = CornerRelief::from_value(self.steps[3].value);
+                let count = self.steps[4].value as usize;
+                Box::new(RectangularHole::new(width, height, corner_radius, corner_style, count))
+            }
+
+            Features::PolygonHoleFeature => {
+                let sides = self.steps[0].value as usize;
+                let size = self.steps[1].value;
+                let across_corners = self.steps[2].value != 0.0;
+                let count = self.steps[3].value as usize;
+                Box::new(PolygonHole::new(sides, size, across_corners, count))
+            }
+
+            Features::KeyholeFeature => {
+                let large_diameter = self.steps[0].value;
+                let slot_width = self.steps[1].value;
+                let slot_length = self.steps[2].value;
+                let count = self.steps[3].value as usize;
+                Box::new(Keyhole::new(large_diameter, slot_width, slot_length, count))
+            }
+
+            Features::DHoleFeature => {
+                let diameter = self.steps[0].value;
+                let across_flats = self.steps[1].value;
+                let flats = self.steps[2].value as usize;
+                let count = self.steps[3].value as usize;
+                Box::new(DHole::new(diameter, across_flats, flats, count))
+            }
+
+            Features::EllipticalHoleFeature => {
                 let width = self.steps[0].value;
                 let height = self.steps[1].value;
                 let count = self.steps[2].value as usize;
-                Box::new(RectangularHole::new(width, height, count))
+                Box::new(EllipticalHole::new(width, height, count))
+            }
+
+            Features::SegmentHoleFeature => {
+                let width = self.steps[0].value;
+                let height = self.steps[1].value;
+                let segment_height = self.steps[2].value;
+                let count = self.steps[3].value as usize;
+                Box::new(SegmentHole::new(width, height, segment_height, count))
+            }
+
+            Features::PolarHolePatternFeature => {
+                let diameter = self.steps[0].value;
+                let holes = self.steps[1].value as usize;
+                let pitch_circle_diameter = self.steps[2].value;
+                let start_angle = self.steps[3].value;
+                let center = (self.steps[4].value, self.steps[5].value);
+                Box::new(HolePattern::new(diameter, PatternLayout::Polar { holes, pitch_circle_diameter, start_angle, center }))
+            }
+
+            Features::GridHolePatternFeature => {
+                let diameter = self.steps[0].value;
+                let columns = self.steps[1].value as usize;
+                let rows = self.steps[2].value as usize;
+                let column_pitch = self.steps[3].value;
+                let row_pitch = self.steps[4].value;
+                let origin = (self.steps[5].value, self.steps[6].value);
+                Box::new(HolePattern::new(diameter, PatternLayout::Grid { columns, rows, column_pitch, row_pitch, origin }))
             }
 
             Features::FilletFeature => {
@@ -258,9 +800,22 @@ impl FeatureAdditionPath {
             }
 
             Features::ChamferFeature => {
-                let size = self.steps[0].value;
-                let count = self.steps[1].value as usize;
-                Box::new(Chamfer::new(size, count))
+                let leg = self.steps[0].value;
+                let second_size = self.steps[1].value;
+                let count = self.steps[3].value as usize;
+                match self.steps[2].value as i32 {
+                    1 => { Box::new(Chamfer::from_angle(leg, second_size, count)) }
+                    // an empty second leg makes a symmetric chamfer
+                    _ if second_size == 0.0 => { Box::new(Chamfer::new(leg, leg, count)) }
+                    _ => { Box::new(Chamfer::new(leg, second_size, count)) }
+                }
+            }
+
+            Features::InternalCornerFeature => {
+                let radius = self.steps[0].value;
+                let relief = CornerRelief::from_value(self.steps[1].value);
+                let count = self.steps[2].value as usize;
+                Box::new(InternalCorner::new(radius, relief, count))
             }
 
             Features::SlopeFeature => {
@@ -278,9 +833,14 @@ impl FeatureAdditionPath {
             }
 
             Features::NotchFeature => {
-                let depth = self.steps[0].value;
-                let count = self.steps[1].value as usize;
-                Box::new(Notch::new(depth, count))
+                let width = self.steps[0].value;
+                let depth = self.steps[1].value;
+                let wall_angle = self.steps[2].value;
+                let bottom = NotchBottom::from_value(self.steps[3].value);
+                let relief_radius = self.steps[4].value;
+                let relief = CornerRelief::from_value(self.steps[5].value);
+                let count = self.steps[6].value as usize;
+                Box::new(Notch::new(width, depth, wall_angle, bottom, relief_radius, relief, count))
             }
 
             Features::SawtoothFeature => {
@@ -313,6 +873,47 @@ impl FeatureAdditionPath {
                 Box::new(CompositeSlope::new(height, angle, slope_type, slope_direction, slope_id, count))
             }
 
+            Features::EdgeSlotFeature => {
+                let width = self.steps[0].value;
+                let depth = self.steps[1].value;
+                let full_radius = self.steps[2].value != 0.0;
+                let count = self.steps[3].value as usize;
+                Box::new(EdgeSlot::new(width, depth, full_radius, count))
+            }
+
+            Features::DovetailFeature => {
+                let depth = self.steps[0].value;
+                let opening_width = self.steps[1].value;
+                let angle = self.steps[2].value;
+                let count = self.steps[3].value as usize;
+                Box::new(Dovetail::new(depth, opening_width, angle, count))
+            }
+
+            Features::TSlotFeature => {
+                let neck_width = self.steps[0].value;
+                let neck_depth = self.steps[1].value;
+                let head_width = self.steps[2].value;
+                let head_depth = self.steps[3].value;
+                let count = self.steps[4].value as usize;
+                Box::new(TSlot::new(neck_width, neck_depth, head_width, head_depth, count))
+            }
+
+            Features::FreeFormEdgeFeature => {
+                let points = FreeFormEdge::parse_points(&self.steps[0].value_input).unwrap_or_default();
+                let smoothed = self.steps[1].value != 0.0;
+                let count = self.steps[2].value as usize;
+                Box::new(FreeFormEdge::new(points, smoothed, count))
+            }
+
+            Features::PeriodicEdgeFeature => {
+                let profile = PeriodicProfile::from_value(self.steps[0].value);
+                let edge_length = self.steps[1].value;
+                let pitch = self.steps[2].value;
+                let amplitude = self.steps[3].value;
+                let count = self.steps[4].value as usize;
+                Box::new(PeriodicEdge::new(profile, edge_length, pitch, amplitude, count))
+            }
+
             Features::ArcFeature => {
                 let radius = self.steps[0].value;
                 let height = self.steps[1].value;
@@ -327,6 +928,24 @@ impl FeatureAdditionPath {
                 Box::new(Ellipse::new(width, height, count))
             }
 
+            Features::EllipticalArcFeature => {
+                let width = self.steps[0].value;
+                let height = self.steps[1].value;
+                let start_angle = self.steps[2].value;
+                let end_angle = self.steps[3].value;
+                let count = self.steps[4].value as usize;
+                Box::new(EllipticalArc::new(width, height, start_angle, end_angle, count))
+            }
+
+            Features::GearFeature => {
+                let module = Gear::module_from(self.steps[0].value, self.steps[1].value != 0.0);
+                let teeth = self.steps[2].value as usize;
+                let pressure_angle = self.steps[3].value;
+                let internal = self.steps[4].value != 0.0;
+                let count = self.steps[5].value as usize;
+                Box::new(Gear::new(module, teeth, pressure_angle, internal, count))
+            }
+
             Features::OtherFeatureFeature => {
                 let modification = self.steps[0].value;
                 let count = self.steps[1].value as usize;
@@ -338,6 +957,19 @@ impl FeatureAdditionPath {
 
 
 
+/// Denotes what kind of input a feature addition step takes.
+#[derive(Clone, Copy, PartialEq)]
+pub enum StepKind {
+    /// A number that is zero or more.
+    Number,
+    /// A number that can be negative.
+    Signed,
+    /// Free text that the feature reads itself.
+    Text,
+}
+
+
+
 /// A step in a feature addition path.
 pub struct FeatureAdditionStep {
     /// One field for a feature of a given type.
@@ -346,10 +978,18 @@ pub struct FeatureAdditionStep {
     value: f64,
     /// The user input for the field.
     pub value_input: String,
+    /// What kind of input the field takes.
+    kind: StepKind,
 }
 impl FeatureAdditionStep {
     /// Creates a new step.
-    pub fn new(field: String) -> Self { Self { field, value: 0.0, value_input: "".to_string() } }
+    pub fn new(field: String) -> Self { Self { field, value: 0.0, value_input: "".to_string(), kind: StepKind::Number } }
+
+    /// Creates a new step that accepts negative values.
+    pub fn signed(field: String) -> Self { Self { kind: StepKind::Signed, ..Self::new(field) } }
+
+    /// Creates a new step that takes text instead of a number.
+    pub fn text(field: String) -> Self { Self { kind: StepKind::Text, ..Self::new(field) } }
 
     /// Updates the value of the field.
     pub fn update_value_input(&mut self, new_value_input: String) { self.value_input = new_value_input; }
@@ -360,129 +1000,1088 @@ impl FeatureAdditionStep {
 
 
 
-/// The main Body that contains a list of features.
-/// Together the body and its features make up a full shape.
-/// Every feature should fit inside the body's width and height bounds.
-pub struct Body {
-    /// The name of the Body.
-    pub name: String,
-    /// The overall width of the shape.
-    pub width: f64,
-    /// The overall height of the shape.
-    pub height: f64,
-    /// The list of modifying features.
-    pub features: Vec<Box<dyn Feature>>,
+/// Denotes the base shape a body is cut from before any features are added.
+/// Every shape sits in the body's width and height bounds, with positions measured from the bottom left corner.
+#[derive(Clone, Copy, PartialEq)]
+pub enum BodyShape {
+    /// A width by height rectangle.
+    Rectangle,
+    /// A disc with the body's width as its diameter.
+    Circle,
+    /// A ring with the body's width as its outer diameter.
+    Annulus { inner_diameter: f64 },
+    /// A regular polygon with a flat bottom edge, centered in a square of its across-corners diameter.
+    RegularPolygon { sides: usize },
+    /// A right triangle with its right angle in the bottom left corner and the body's width and height as its legs.
+    RightTriangle,
 }
-impl Body {
-    /// Creates a new Body.
-    pub fn new() -> Self { Body {name: "".to_string(), width: 0.0, height: 0.0, features: Vec::new() } }
-
-    /// Renames the Body.
-    pub fn rename(&mut self, new_name: String) { self.name = new_name; }
-
-    /// Sets the overall width of the body.
-    pub fn set_width(&mut self, width: f64) { self.width = width; }
+impl BodyShape {
+    /// Returns every shape, with empty dimensions, in the order they are offered.
+    pub fn all() -> Vec<BodyShape> {
+        vec![
+            BodyShape::Rectangle,
+            BodyShape::Circle,
+            BodyShape::Annulus { inner_diameter: 0.0 },
+            BodyShape::RegularPolygon { sides: 0 },
+            BodyShape::RightTriangle,
+        ]
+    }
 
-    /// Sets the overall height of the body.
-    pub fn set_height(&mut self, height: f64) { self.height = height; }
+    /// Returns the name of the shape.
+    pub fn name(&self) -> String {
+        match self {
+            BodyShape::Rectangle => { "rectangle".to_string() }
+            BodyShape::Circle => { "circle".to_string() }
+            BodyShape::Annulus { .. } => { "annulus".to_string() }
+            BodyShape::RegularPolygon { .. } => { "regular polygon".to_string() }
+            BodyShape::RightTriangle => { "right triangle".to_string() }
+        }
+    }
 
-    /// Adds a feature.
-    pub fn add(&mut self, feature: Box<dyn Feature>) { self.features.push(feature); }
+    /// Returns the shape with the given name, with empty dimensions.
+    pub fn from_name(name: &str) -> Option<BodyShape> {
+        BodyShape::all().into_iter().find(|shape| shape.name() == name.trim())
+    }
 
-    /// Removes a feature
-    pub fn remove(&mut self, index: usize) {
-        if self.features.len() >= index { self.features.remove(index); }
+    /// Returns the names of the dimensions entered when the body is added.
+    pub fn dimension_names(&self) -> Vec<&'static str> {
+        match self {
+            BodyShape::Rectangle | BodyShape::RightTriangle => { vec!["Width", "Height"] }
+            BodyShape::Circle => { vec!["Diameter"] }
+            BodyShape::Annulus { .. } => { vec!["Outer diameter", "Inner diameter"] }
+            BodyShape::RegularPolygon { .. } => { vec!["Corner diameter", "Sides"] }
+        }
     }
 
-    /// Gets the full perimeter of the body with all of its features.
-    pub fn perimeter(&self) -> f64 {
-        let mut perimeter = (self.width + self.height) * 2.0;
+    /// Checks the dimensions entered when the body is added, in the order of the dimension names.
+    pub fn check_dimensions(&self, first: f64, second: f64) -> Result<(), String> {
+        if first <= 0.0 { return Err(format!("the {} must be more than 0", self.dimension_names()[0].to_lowercase())); }
+        match self {
+            BodyShape::Rectangle | BodyShape::RightTriangle if second <= 0.0 => { Err("the height must be more than 0".to_string()) }
+            BodyShape::Annulus { .. } if second <= 0.0 || second >= first => { Err("the inner diameter must be more than 0 and less than the outer diameter".to_string()) }
+            BodyShape::RegularPolygon { .. } if second < 3.0 || second.fract() != 0.0 => { Err("a polygon needs a whole number of at least 3 sides".to_string()) }
+            _ => { Ok(()) }
+        }
+    }
 
-        let mut composite_slope_index = CompositeSlopeIndex::new();
+    /// Returns whether the shape has corners that corner features can be added to.
+    pub fn has_corners(&self) -> bool {
+        !matches!(self, BodyShape::Circle | BodyShape::Annulus { .. })
+    }
 
-        for feature in &self.features {
-            if let Some(composite_slope) = feature.as_any().downcast_ref::<CompositeSlope>() {
-                composite_slope_index.add_height(composite_slope.height, composite_slope.slope_direction, composite_slope.slope_id);
-            }
-            else {
-                perimeter += feature.value();
+    /// Returns the corners of a straight sided shape inside its bounds, counterclockwise from the bottom.
+    pub fn corners(&self, width: f64, height: f64) -> Option<Vec<(f64, f64)>> {
+        match self {
+            BodyShape::Rectangle => { Some(vec![(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)]) }
+            BodyShape::RightTriangle => { Some(vec![(0.0, 0.0), (width, 0.0), (0.0, height)]) }
+            BodyShape::RegularPolygon { sides } => {
+                let radius = width / 2.0;
+                Some((0..*sides)
+                    .map(|index| {
+                        let angle = (-PI / 2.0) + (PI / *sides as f64) + (2.0 * PI * index as f64 / *sides as f64);
+                        (radius + (radius * angle.cos()), (height / 2.0) + (radius * angle.sin()))
+                    })
+                    .collect())
             }
+            BodyShape::Circle | BodyShape::Annulus { .. } => { None }
         }
-
-        perimeter -= composite_slope_index.get_height_differences();
-
-        perimeter
     }
+}
 
-    /// Summarizes the body
-    pub fn summarize(&self) -> Vec<String> {
-        let mut summary = Vec::new();
-        let name = format!("name: {}", &self.name);
-        let width = format!("width: {}, ", &format!("{:.3}", self.width));
-        let height = format!("height: {}", &format!("{:.3}", self.height));
-        let perimeter = format!("perimeter: {}", &format!("{:.3}", self.perimeter()));
-        summary.push(name);
-        summary.push(width);
-        summary.push(height);
-        summary.push(perimeter);
 
-        summary
+
+/// Denotes one of the body's outside corners.
+#[derive(Clone, Copy, PartialEq)]
+pub enum BodyCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+impl BodyCorner {
+    /// Returns every corner.
+    pub fn all() -> Vec<BodyCorner> {
+        vec![BodyCorner::TopLeft, BodyCorner::TopRight, BodyCorner::BottomLeft, BodyCorner::BottomRight]
     }
 
-    /// Prints a pdf summary of the body.
-    pub fn print_pdf_summary(&self, project: String) -> Result<(), Box<dyn std::error::Error>> {
-        let font_family = fonts::from_files(
-            "./fonts",
-            "LiberationSans",
-            None
-        )?;
-        // pdf
-        let mut pdf = genpdf::Document::new(font_family);
-
-        // title
-        let title = format!("{} - {}", &project, &self.name);
-        pdf.set_title(&self.name);
-
-        // margins
-        let mut decorator = SimplePageDecorator::new();
-        decorator.set_margins(10);
-        pdf.set_page_decorator(decorator);
-
-        // building the page
-        let mut details = Vec::new();
-        details.push(title);
-        details.push("".to_string());
-        details.append(&mut self.summarize());
-        details.push("".to_string());
-        for feature in &self.features {
-            details.push("".to_string());
-            let feature_summary = feature.summarize();
-            details.extend(feature_summary);
+    /// Returns the short name of the corner.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BodyCorner::TopLeft => "TL",
+            BodyCorner::TopRight => "TR",
+            BodyCorner::BottomLeft => "BL",
+            BodyCorner::BottomRight => "BR",
         }
+    }
+}
 
-        for detail in details { pdf.push(PdfParagraph::new(detail)); }
 
-        // saving
-        pdf.render_to_file(&format!("./{} - {}.pdf", &project, &self.name))?;
+
+/// Denotes one of the body's edges.
+#[derive(Clone, Copy, PartialEq)]
+pub enum BodyEdge {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+impl BodyEdge {
+    /// Returns every edge.
+    pub fn all() -> Vec<BodyEdge> {
+        vec![BodyEdge::Top, BodyEdge::Right, BodyEdge::Bottom, BodyEdge::Left]
+    }
+
+    /// Returns the name of the edge.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BodyEdge::Top => "top",
+            BodyEdge::Right => "right",
+            BodyEdge::Bottom => "bottom",
+            BodyEdge::Left => "left",
+        }
+    }
+
+    /// Returns the corners at the start and the end of the edge, with offsets running from the left or bottom end.
+    pub fn corners(&self) -> (BodyCorner, BodyCorner) {
+        match self {
+            BodyEdge::Top => (BodyCorner::TopLeft, BodyCorner::TopRight),
+            BodyEdge::Right => (BodyCorner::BottomRight, BodyCorner::TopRight),
+            BodyEdge::Bottom => (BodyCorner::BottomLeft, BodyCorner::BottomRight),
+            BodyEdge::Left => (BodyCorner::BottomLeft, BodyCorner::TopLeft),
+        }
+    }
+}
+
+
+
+/// Denotes how a feature is placed on the body.
+#[derive(Clone, Copy, PartialEq)]
+pub enum PlacementKind {
+    /// By the x/y position of its center.
+    Point,
+    /// By the corner it modifies.
+    Corner,
+    /// By the edge it sits on and how far along the edge it starts.
+    Edge,
+}
+impl PlacementKind {
+    /// Explains how placements of this kind are typed.
+    pub fn hint(&self) -> &'static str {
+        match self {
+            PlacementKind::Point => "x,y of each center from the bottom left corner, such as 10,5 30,5",
+            PlacementKind::Corner => "TL, TR, BL or BR for each corner, such as TL BR",
+            PlacementKind::Edge => "edge@offset for each, such as bottom@10 top@0 (offsets run from the left or bottom end)",
+        }
+    }
+}
+
+
+
+/// Where one instance of a feature sits on the body.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Placement {
+    /// The center of the feature, measured from the body's bottom left corner.
+    Point { x: f64, y: f64 },
+    /// The corner the feature modifies.
+    Corner(BodyCorner),
+    /// The edge the feature sits on, and the distance from the edge's left or bottom end to where the feature starts.
+    Edge { edge: BodyEdge, offset: f64 },
+}
+impl Placement {
+    /// Reads a list of placements separated by spaces, such as "10,5 30,5", "TL BR" or "bottom@10 top@0".
+    /// A blank list leaves every instance unplaced.
+    pub fn parse_list(kind: PlacementKind, text: &str) -> Result<Vec<Placement>, String> {
+        text.split_whitespace().map(|item| Placement::parse(kind, item)).collect()
+    }
+
+    /// Reads one placement.
+    fn parse(kind: PlacementKind, text: &str) -> Result<Placement, String> {
+        match kind {
+            PlacementKind::Point => {
+                let (x, y) = text.split_once(',').ok_or(format!("\"{}\" is not a position written as x,y", text))?;
+                let x = x.parse::<f64>().map_err(|_| format!("\"{}\" is not a number", x))?;
+                let y = y.parse::<f64>().map_err(|_| format!("\"{}\" is not a number", y))?;
+                Ok(Placement::Point { x, y })
+            }
+            PlacementKind::Corner => {
+                BodyCorner::all()
+                    .into_iter()
+                    .find(|corner| corner.name().eq_ignore_ascii_case(text))
+                    .map(Placement::Corner)
+                    .ok_or(format!("\"{}\" is not a corner (TL, TR, BL or BR)", text))
+            }
+            PlacementKind::Edge => {
+                let (edge, offset) = text.split_once('@').ok_or(format!("\"{}\" is not an edge written as edge@offset", text))?;
+                let edge = BodyEdge::all()
+                    .into_iter()
+                    .find(|candidate| candidate.name().eq_ignore_ascii_case(edge))
+                    .ok_or(format!("\"{}\" is not an edge (top, right, bottom or left)", edge))?;
+                let offset = offset.parse::<f64>().map_err(|_| format!("\"{}\" is not a number", offset))?;
+                Ok(Placement::Edge { edge, offset })
+            }
+        }
+    }
+
+    /// Writes the placement the way it is read.
+    pub fn text(&self) -> String {
+        match self {
+            Placement::Point { x, y } => { format!("{},{}", x, y) }
+            Placement::Corner(corner) => { corner.name().to_string() }
+            Placement::Edge { edge, offset } => { format!("{}@{}", edge.name(), offset) }
+        }
+    }
+
+    /// Writes a list of placements the way it is read.
+    pub fn list_text(placements: &[Placement]) -> String {
+        placements.iter().map(|placement| placement.text()).collect::<Vec<String>>().join(" ")
+    }
+}
+
+
+
+/// A feature that can be placed, together with where each of its instances sits on the body.
+/// Everything but the placements is the wrapped feature's own.
+pub struct Placed {
+    /// The feature being placed.
+    feature: Box<dyn Feature>,
+    /// Where each instance sits on the body, if it has been placed.
+    placements: Vec<Placement>,
+}
+impl Placed {
+    /// Wraps a feature with its placements.
+    pub fn new(feature: Box<dyn Feature>, placements: Vec<Placement>) -> Self { Self { feature, placements } }
+}
+impl Feature for Placed {
+    /// The type of the wrapped feature.
+    fn shape(&self) -> Features { self.feature.shape() }
+
+    /// Allows field access on the wrapped feature.
+    fn as_any(&self) -> &dyn Any { self.feature.as_any() }
+
+    /// The count of the wrapped feature.
+    fn count(&self) -> usize { self.feature.count() }
+
+    /// Whether the wrapped feature's summary fits on one line.
+    fn single_line(&self) -> bool { self.feature.single_line() }
+
+    /// The wrapped feature's summary line.
+    fn summarize_feature(&self) -> String { self.feature.summarize_feature() }
+
+    /// The wrapped feature's dimensions.
+    fn summarize_dimensions(&self) -> Vec<String> { self.feature.summarize_dimensions() }
+
+    /// Where each instance sits on the body.
+    fn placements(&self) -> &[Placement] { &self.placements }
+
+    /// The area one instance of the wrapped feature takes up.
+    fn extent(&self) -> Option<(f64, f64)> { self.feature.extent() }
+
+    /// The wrapped feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> { self.feature.geometry() }
+
+    /// The wrapped feature's parameters.
+    fn parameters(&self) -> Vec<(String, String)> { self.feature.parameters() }
+
+    /// The wrapped feature's modification summary.
+    fn summarize_modification(&self) -> String { self.feature.summarize_modification() }
+
+    /// The perimeter modification of the wrapped feature.
+    fn value(&self) -> f64 { self.feature.value() }
+
+    /// The pierces the wrapped feature needs.
+    fn pierces(&self) -> usize { self.feature.pierces() }
+}
+
+
+
+/// The smallest difference between a formula and the drawn geometry that verification reports.
+const VERIFY_TOLERANCE: f64 = 0.01;
+/// The share of a length that verification allows for curves drawn as fine straight lines.
+const VERIFY_RELATIVE_TOLERANCE: f64 = 0.0001;
+
+/// The main Body that contains a list of features.
+/// Together the body and its features make up a full shape.
+/// Every feature should fit inside the body's width and height bounds.
+pub struct Body {
+    /// The name of the Body.
+    pub name: String,
+    /// The overall width of the shape.
+    pub width: f64,
+    /// The overall height of the shape.
+    pub height: f64,
+    /// The base shape the body is cut from.
+    pub shape: BodyShape,
+    /// How many of the body are made.
+    pub quantity: usize,
+    /// The width of the cut the machine makes, or the diameter of its tool.
+    pub kerf: f64,
+    /// The list of modifying features.
+    pub features: Vec<Box<dyn Feature>>,
+}
+impl Body {
+    /// Creates a new Body.
+    pub fn new() -> Self { Body {name: "".to_string(), width: 0.0, height: 0.0, shape: BodyShape::Rectangle, quantity: 1, kerf: 0.0, features: Vec::new() } }
+
+    /// Renames the Body.
+    pub fn rename(&mut self, new_name: String) { self.name = new_name; }
+
+    /// Sets the overall width of the body.
+    pub fn set_width(&mut self, width: f64) { self.width = width; }
+
+    /// Sets the overall height of the body.
+    pub fn set_height(&mut self, height: f64) { self.height = height; }
+
+    /// Sets the base shape of the body.
+    pub fn set_shape(&mut self, shape: BodyShape) { self.shape = shape; }
+
+    /// Sets the base shape and its dimensions, given in the order of the shape's dimension names.
+    pub fn set_shape_dimensions(&mut self, shape: BodyShape, first: f64, second: f64) {
+        match shape {
+            BodyShape::Rectangle | BodyShape::RightTriangle => {
+                self.set_width(first);
+                self.set_height(second);
+                self.set_shape(shape);
+            }
+            BodyShape::Circle => {
+                self.set_width(first);
+                self.set_height(first);
+                self.set_shape(shape);
+            }
+            BodyShape::Annulus { .. } => {
+                self.set_width(first);
+                self.set_height(first);
+                self.set_shape(BodyShape::Annulus { inner_diameter: second });
+            }
+            BodyShape::RegularPolygon { .. } => {
+                self.set_width(first);
+                self.set_height(first);
+                self.set_shape(BodyShape::RegularPolygon { sides: second as usize });
+            }
+        }
+    }
+
+    /// Returns whether the body's base shape has the given corner.
+    /// A right triangle has no top right corner, and only rectangles and right triangles have named corners.
+    pub fn has_corner(&self, corner: BodyCorner) -> bool {
+        match self.shape {
+            BodyShape::Rectangle => { true }
+            BodyShape::RightTriangle => { corner != BodyCorner::TopRight }
+            _ => { false }
+        }
+    }
+
+    /// Returns the length of one of the body's edges, if its base shape has that edge.
+    /// A right triangle's hypotenuse and a regular polygon's edges other than its flat bottom are not named edges.
+    pub fn edge_length(&self, edge: BodyEdge) -> Option<f64> {
+        match (self.shape, edge) {
+            (BodyShape::Rectangle, BodyEdge::Top) | (BodyShape::Rectangle, BodyEdge::Bottom) | (BodyShape::RightTriangle, BodyEdge::Bottom) => { Some(self.width) }
+            (BodyShape::Rectangle, BodyEdge::Left) | (BodyShape::Rectangle, BodyEdge::Right) | (BodyShape::RightTriangle, BodyEdge::Left) => { Some(self.height) }
+            (BodyShape::RegularPolygon { sides }, BodyEdge::Bottom) if sides >= 3 => { Some(self.width * (PI / sides as f64).sin()) }
+            _ => { None }
+        }
+    }
+
+    /// Returns the left end of the body's bottom edge, which a regular polygon raises and insets from the corner of its bounds.
+    fn bottom_edge_start(&self) -> (f64, f64) {
+        match self.shape {
+            BodyShape::RegularPolygon { .. } => {
+                self.shape.corners(self.width, self.height).and_then(|corners| corners.last().copied()).unwrap_or((0.0, 0.0))
+            }
+            _ => { (0.0, 0.0) }
+        }
+    }
+
+    /// Checks that a circle is inside the body's base shape.
+    pub fn contains_circle(&self, x: f64, y: f64, radius: f64) -> bool {
+        match self.shape {
+            BodyShape::Circle => { (x - self.width / 2.0).hypot(y - self.height / 2.0) + radius <= self.width / 2.0 }
+            BodyShape::Annulus { inner_diameter } => {
+                let distance = (x - self.width / 2.0).hypot(y - self.height / 2.0);
+                distance + radius <= self.width / 2.0 && distance - radius >= inner_diameter / 2.0
+            }
+            _ => {
+                // inside every edge of the counterclockwise outline by at least the radius
+                let corners = self.shape.corners(self.width, self.height).unwrap_or_default();
+                (0..corners.len()).all(|index| {
+                    let (x0, y0) = corners[index];
+                    let (x1, y1) = corners[(index + 1) % corners.len()];
+                    let length = (x1 - x0).hypot(y1 - y0);
+                    (((x1 - x0) * (y - y0)) - ((y1 - y0) * (x - x0))) / length >= radius
+                })
+            }
+        }
+    }
+
+    /// Sets how many of the body are made.
+    pub fn set_quantity(&mut self, quantity: usize) { self.quantity = quantity; }
+
+    /// Sets the width of the cut the machine makes.
+    pub fn set_kerf(&mut self, kerf: f64) { self.kerf = kerf; }
+
+    /// Checks if the body has not been given a size or any features yet.
+    pub fn is_blank(&self) -> bool { self.width <= 0.0 && self.features.is_empty() }
+
+    /// Adds a feature.
+    pub fn add(&mut self, feature: Box<dyn Feature>) { self.features.push(feature); }
+
+    /// Removes a feature
+    #[allow(dead_code)]
+    pub fn remove(&mut self, index: usize) {
+        if index < self.features.len() { self.features.remove(index); }
+    }
+
+    /// Checks that a new feature fits inside the body's width and height bounds.
+    /// Only features with known positions can be checked.
+    pub fn check_fit(&self, feature: &dyn Feature) -> Result<(), String> {
+        if feature.shape().is_corner() && !self.shape.has_corners() {
+            return Err(format!("the body's {} shape has no corners for a {}", self.shape.name(), feature.shape().name()));
+        }
+        for placement in feature.placements() {
+            match placement {
+                Placement::Point { x, y } => {
+                    if !self.contains_circle(*x, *y, 0.0) { return Err(format!("({:.3}, {:.3}) is outside the body", x, y)); }
+                }
+                Placement::Corner(corner) => {
+                    if !self.has_corner(*corner) { return Err(format!("the body's {} shape has no {} corner", self.shape.name(), corner.name())); }
+                }
+                Placement::Edge { edge, offset } => {
+                    let length = self.edge_length(*edge).ok_or(format!("the body's {} shape has no {} edge", self.shape.name(), edge.name()))?;
+                    if *offset < 0.0 || *offset > length { return Err(format!("an offset of {:.3} is off the {:.3} long {} edge", offset, length, edge.name())); }
+                }
+            }
+        }
+        if let Some(gear) = feature.as_any().downcast_ref::<Gear>() {
+            if gear.is_external() && self.outline_gear().is_some() { return Err("the body already has an external gear outline".to_string()); }
+            if gear.is_external() && gear.blank_diameter() > self.width.min(self.height) {
+                return Err(format!("the gear's {:.3} blank does not fit in the body", gear.blank_diameter()));
+            }
+        }
+        if let Some(pattern) = feature.as_any().downcast_ref::<HolePattern>() {
+            let radius = pattern.diameter() / 2.0;
+            for (index, (x, y)) in pattern.positions().iter().enumerate() {
+                if !self.contains_circle(*x, *y, radius) {
+                    return Err(format!("hole {} at ({:.3}, {:.3}) crosses the body edge", index + 1, x, y));
+                }
+            }
+        }
         Ok(())
     }
+
+    /// Finds placed features that run into each other or past the body, which the perimeter would otherwise double count.
+    /// Holes are compared by the box around them, or by their circles when both are round, and edge features by the box they cut into the body.
+    pub fn interferences(&self) -> Vec<String> {
+        // a placed hole's center, size, whether it is round and a label
+        type PlacedHole = ((f64, f64), (f64, f64), bool, String);
+        // a placed edge feature's edge, span along the edge, the box it cuts into the body and a label
+        type PlacedEdgeCut = (BodyEdge, (f64, f64), ((f64, f64), (f64, f64)), String);
+
+        let mut warnings = Vec::new();
+
+        // corners taken by more than one corner feature
+        let mut corners: Vec<(BodyCorner, String)> = Vec::new();
+        for feature in &self.features {
+            for placement in feature.placements() {
+                let taken = match placement {
+                    Placement::Corner(corner) => { vec![*corner] }
+                    // slopes and cliffs are placed on an edge, and take the corner at whichever end of it they start or finish
+                    Placement::Edge { edge, offset } if feature.shape().is_corner() => {
+                        let (start, end) = edge.corners();
+                        let mut taken = Vec::new();
+                        if offset.abs() < 1e-9 { taken.push(start); }
+                        if let (Some(edge_length), Some((length, _))) = (self.edge_length(*edge), feature.extent())
+                            && (offset + length - edge_length).abs() < 1e-9 { taken.push(end); }
+                        taken
+                    }
+                    _ => { Vec::new() }
+                };
+                for corner in taken {
+                    if let Some((_, other)) = corners.iter().find(|(taken, _)| *taken == corner) {
+                        warnings.push(format!("the {} and the {} are both on the {} corner", other, feature.shape().name(), corner.name()));
+                    }
+                    corners.push((corner, feature.shape().name()));
+                }
+            }
+        }
+
+        // every placed hole
+        let mut holes: Vec<PlacedHole> = Vec::new();
+        for feature in &self.features {
+            if let Some(pattern) = feature.as_any().downcast_ref::<HolePattern>() {
+                for (index, center) in pattern.positions().into_iter().enumerate() {
+                    holes.push((center, (pattern.diameter(), pattern.diameter()), true, format!("{} hole {}", feature.shape().name(), index + 1)));
+                }
+                continue;
+            }
+            let Some(extent) = feature.extent() else { continue; };
+            let round = matches!(feature.shape(), Features::CircularHoleFeature);
+            for placement in feature.placements() {
+                if let Placement::Point { x, y } = placement {
+                    holes.push(((*x, *y), extent, round, format!("{} at ({:.3}, {:.3})", feature.shape().name(), x, y)));
+                }
+            }
+        }
+
+        // every placed edge feature
+        let mut edge_cuts: Vec<PlacedEdgeCut> = Vec::new();
+        let longest_edge = [BodyEdge::Top, BodyEdge::Right, BodyEdge::Bottom, BodyEdge::Left]
+            .into_iter()
+            .filter_map(|edge| self.edge_length(edge))
+            .fold(0.0, f64::max);
+        for feature in &self.features {
+            if feature.shape().placement_kind() != Some(PlacementKind::Edge) { continue; }
+            let Some((length, depth)) = feature.extent() else { continue; };
+            if feature.placements().is_empty() && longest_edge > 0.0 && length > longest_edge {
+                warnings.push(format!("the {} is {:.3} long, which is longer than every edge of the body", feature.shape().name(), length));
+            }
+            for placement in feature.placements() {
+                let Placement::Edge { edge, offset } = placement else { continue; };
+                let label = format!("{} at {}", feature.shape().name(), placement.text());
+                if let Some(edge_length) = self.edge_length(*edge) && offset + length > edge_length {
+                    warnings.push(format!("the {} runs {:.3} past the end of the {:.3} long {} edge", label, offset + length - edge_length, edge_length, edge.name()));
+                }
+                let (start, end) = (*offset, offset + length);
+                let area = match edge {
+                    BodyEdge::Bottom => {
+                        let (x, y) = self.bottom_edge_start();
+                        ((x + start, x + end), (y, y + depth))
+                    }
+                    BodyEdge::Top => { ((start, end), (self.height - depth, self.height)) }
+                    BodyEdge::Left => { ((0.0, depth), (start, end)) }
+                    BodyEdge::Right => { ((self.width - depth, self.width), (start, end)) }
+                };
+                edge_cuts.push((*edge, (start, end), area, label));
+            }
+        }
+
+        let overlaps = |(low_a, high_a): (f64, f64), (low_b, high_b): (f64, f64)| low_a < high_b - 1e-9 && low_b < high_a - 1e-9;
+        let hole_area = |((x, y), (width, height)): ((f64, f64), (f64, f64))| ((x - width / 2.0, x + width / 2.0), (y - height / 2.0, y + height / 2.0));
+
+        for (index, (center, extent, round, label)) in holes.iter().enumerate() {
+            // holes running past the body's outline
+            let inside = match self.shape {
+                BodyShape::Circle | BodyShape::Annulus { .. } => { self.contains_circle(center.0, center.1, extent.0.max(extent.1) / 2.0) }
+                _ => {
+                    let (across, up) = hole_area((*center, *extent));
+                    [(across.0, up.0), (across.1, up.0), (across.0, up.1), (across.1, up.1)].iter().all(|(x, y)| self.contains_circle(*x, *y, 0.0))
+                }
+            };
+            if !inside { warnings.push(format!("the {} runs past the body's outline", label)); }
+
+            // holes overlapping later holes
+            for (other_center, other_extent, other_round, other_label) in holes.iter().skip(index + 1) {
+                let overlapping = if *round && *other_round {
+                    (center.0 - other_center.0).hypot(center.1 - other_center.1) < (extent.0 + other_extent.0) / 2.0 - 1e-9
+                }
+                else {
+                    let (across, up) = hole_area((*center, *extent));
+                    let (other_across, other_up) = hole_area((*other_center, *other_extent));
+                    overlaps(across, other_across) && overlaps(up, other_up)
+                };
+                if overlapping { warnings.push(format!("the {} overlaps the {}", label, other_label)); }
+            }
+
+            // holes running into edge features
+            for (_, _, (across, up), cut_label) in &edge_cuts {
+                let (hole_across, hole_up) = hole_area((*center, *extent));
+                if overlaps(hole_across, *across) && overlaps(hole_up, *up) { warnings.push(format!("the {} runs into the {}", label, cut_label)); }
+            }
+        }
+
+        // edge features overlapping on the same edge
+        for (index, (edge, span, _, label)) in edge_cuts.iter().enumerate() {
+            for (other_edge, other_span, _, other_label) in edge_cuts.iter().skip(index + 1) {
+                if edge == other_edge && overlaps(*span, *other_span) { warnings.push(format!("the {} overlaps the {}", label, other_label)); }
+            }
+        }
+
+        warnings
+    }
+
+    /// Returns every feature, for the calculations that can also run on only some of them.
+    fn feature_list(&self) -> Vec<&dyn Feature> { self.features.iter().map(|feature| feature.as_ref()).collect() }
+
+    /// Returns every feature but the one at an index.
+    fn features_without(&self, index: usize) -> Vec<&dyn Feature> {
+        self.features.iter().enumerate().filter(|(other, _)| *other != index).map(|(_, feature)| feature.as_ref()).collect()
+    }
+
+    /// Returns the external gear whose outline replaces the body's perimeter, if there is one.
+    pub fn outline_gear(&self) -> Option<&Gear> { Self::outline_gear_in(&self.feature_list()) }
+
+    /// Returns the external gear among some features, if there is one.
+    fn outline_gear_in<'a>(features: &[&'a dyn Feature]) -> Option<&'a Gear> {
+        features
+            .iter()
+            .filter_map(|feature| feature.as_any().downcast_ref::<Gear>())
+            .find(|gear| gear.is_external())
+    }
+
+    /// Gets the perimeter of the body before any features are added.
+    /// An external gear cuts the body from a circular blank, so its tooth outline is used instead of the shape's outline.
+    pub fn base_perimeter(&self) -> f64 { self.base_perimeter_with(&self.feature_list()) }
+
+    /// Gets the perimeter of the body before any features are added, with only some of its features.
+    fn base_perimeter_with(&self, features: &[&dyn Feature]) -> f64 {
+        let outline = match (Self::outline_gear_in(features), self.shape) {
+            (Some(gear), _) => { gear.outline_length() }
+            (None, BodyShape::Rectangle) => { (self.width + self.height) * 2.0 }
+            (None, BodyShape::Circle) | (None, BodyShape::Annulus { .. }) => { PI * self.width }
+            (None, BodyShape::RegularPolygon { sides }) => { sides as f64 * self.width * (PI / sides as f64).sin() }
+            (None, BodyShape::RightTriangle) => { self.width + self.height + self.width.hypot(self.height) }
+        };
+        match self.shape {
+            BodyShape::Annulus { inner_diameter } => { outline + (PI * inner_diameter) }
+            _ => { outline }
+        }
+    }
+
+    /// Gets the full perimeter of the body with all of its features.
+    pub fn perimeter(&self) -> f64 { self.perimeter_with(&self.feature_list()) }
+
+    /// Gets the perimeter of the body with only some of its features.
+    fn perimeter_with(&self, features: &[&dyn Feature]) -> f64 {
+        self.perimeter_breakdown_with(features).iter().map(|(_, value)| value).sum()
+    }
+
+    /// Gets the perimeter of the body without the feature at an index.
+    fn perimeter_without(&self, index: usize) -> f64 { self.perimeter_with(&self.features_without(index)) }
+
+    /// Gets the length of the path the machine follows, offset by half the kerf outside the outline and inside the holes.
+    /// The offset is measured on the drawn contours. If the body is not fully drawn, or a contour is too small for the kerf,
+    /// every contour is treated as round instead, which is only approximate; the second value says whether that happened.
+    pub fn toolpath_perimeter(&self) -> (f64, bool) {
+        let geometry = self.geometry();
+        if geometry.missing.is_empty() && let Some(toolpath) = geometry.toolpath_length(self.kerf) {
+            return (self.perimeter() + toolpath - geometry.perimeter(), true);
+        }
+        // a round outline grows by pi times the kerf and each round hole shrinks by as much
+        (self.perimeter() + (PI * self.kerf * (2.0 - self.pierces() as f64)), false)
+    }
+
+    /// Gets the perimeter of every copy of the body combined.
+    pub fn extended_perimeter(&self) -> f64 {
+        self.perimeter() * self.quantity as f64
+    }
+
+    /// Gets the toolpath perimeter of every copy of the body combined.
+    pub fn extended_toolpath_perimeter(&self) -> f64 {
+        self.toolpath_perimeter().0 * self.quantity as f64
+    }
+
+    /// Gets how many cuts must be pierced to make one body: one for the outline, one for an annulus's center and one for every hole.
+    pub fn pierces(&self) -> usize {
+        let center = if matches!(self.shape, BodyShape::Annulus { .. }) { 1 } else { 0 };
+        1 + center + self.features.iter().map(|feature| feature.pierces()).sum::<usize>()
+    }
+
+    /// Lists each part of the perimeter: the base body, every feature's modification and the composite slope height differences.
+    pub fn perimeter_breakdown(&self) -> Vec<(String, f64)> { self.perimeter_breakdown_with(&self.feature_list()) }
+
+    /// Lists each part of the perimeter of the body with only some of its features.
+    fn perimeter_breakdown_with(&self, features: &[&dyn Feature]) -> Vec<(String, f64)> {
+        let mut breakdown = Vec::new();
+        let base = if Self::outline_gear_in(features).is_some() { "body (gear outline)" } else { "body" };
+        breakdown.push((base.to_string(), self.base_perimeter_with(features)));
+
+        let mut composite_slope_index = CompositeSlopeIndex::new();
+
+        for feature in features {
+            if let Some(composite_slope) = feature.as_any().downcast_ref::<CompositeSlope>() {
+                composite_slope_index.add_height(composite_slope.height, composite_slope.slope_direction, composite_slope.slope_id);
+            }
+            else {
+                breakdown.push((feature.summarize_feature(), feature.value()));
+            }
+        }
+
+        if !composite_slope_index.trackers.is_empty() {
+            breakdown.push(("composite slope height differences".to_string(), -composite_slope_index.get_height_differences()));
+        }
+
+        breakdown
+    }
+
+    /// Builds the body's outline and hole contours from its shape and placed features.
+    pub fn geometry(&self) -> BodyGeometry { geometry::body_geometry(self, &self.feature_list()) }
+
+    /// Builds the body's contours without the feature at an index.
+    fn geometry_without(&self, index: usize) -> BodyGeometry { geometry::body_geometry(self, &self.features_without(index)) }
+
+    /// Checks the formula perimeter against the length of the drawn geometry, for the whole body and then feature by feature.
+    /// A feature's share of either is how much it changes when the feature is left out, so composite slope groups and gear outlines are compared as they add up.
+    /// Features that are not fully placed or have no known shape cannot be drawn, so they are listed as not checked.
+    pub fn verify(&self) -> Vec<String> {
+        let tolerance = |length: f64| VERIFY_TOLERANCE.max(length.abs() * VERIFY_RELATIVE_TOLERANCE);
+        let compare = |name: &str, formula: f64, drawn: f64| {
+            if (formula - drawn).abs() > tolerance(drawn) {
+                format!("mismatch: {}: formula {:.3}, drawn {:.3}, off by {:+.3}", name, formula, drawn, formula - drawn)
+            }
+            else {
+                format!("ok: {}: {:.3}", name, formula)
+            }
+        };
+
+        let geometry = self.geometry();
+        let (formula, drawn) = (self.perimeter(), geometry.perimeter());
+        let mut lines = vec![format!("formula perimeter: {:.3}", formula), format!("drawn perimeter: {:.3}", drawn)];
+        if geometry.missing.is_empty() { lines.push(compare("whole body", formula, drawn)); }
+        else { lines.push(format!("not checked: whole body, {} not drawn", geometry.missing.join(", "))); }
+        lines.push("".to_string());
+
+        // the base shape on its own
+        lines.push(compare("body", self.perimeter_with(&[]), geometry::body_geometry(self, &[]).perimeter()));
+
+        // each feature's share
+        for index in 0..self.features.len() {
+            let name = self.features[index].summarize_feature();
+            if self.features[index].geometry().is_none() {
+                lines.push(format!("not checked: {}, no known shape", name));
+                continue;
+            }
+            if self.features[index].shape().placement_kind().is_some() && self.features[index].placements().len() < self.features[index].count() {
+                lines.push(format!("not checked: {}, not fully placed", name));
+                continue;
+            }
+            let (formula_without, drawn_without) = (self.perimeter_without(index), self.geometry_without(index).perimeter());
+            lines.push(compare(&name, formula - formula_without, drawn - drawn_without));
+        }
+
+        lines
+    }
+
+    /// Summarizes the body
+    pub fn summarize(&self) -> Vec<String> {
+        let mut summary = Vec::new();
+        let name = format!("name: {}", &self.name);
+        let shape = format!("shape: {}", self.shape.name());
+        let perimeter = format!("perimeter: {}", &format!("{:.3}", self.perimeter()));
+        let quantity = format!("quantity: {}", self.quantity);
+        summary.push(name);
+        summary.push(shape);
+        match self.shape {
+            BodyShape::Rectangle | BodyShape::RightTriangle => {
+                summary.push(format!("width: {}, ", &format!("{:.3}", self.width)));
+                summary.push(format!("height: {}", &format!("{:.3}", self.height)));
+            }
+            BodyShape::Circle => {
+                summary.push(format!("diameter: {}", &format!("{:.3}", self.width)));
+            }
+            BodyShape::Annulus { inner_diameter } => {
+                summary.push(format!("outer diameter: {}, ", &format!("{:.3}", self.width)));
+                summary.push(format!("inner diameter: {}", &format!("{:.3}", inner_diameter)));
+            }
+            BodyShape::RegularPolygon { sides } => {
+                summary.push(format!("corner diameter: {}, ", &format!("{:.3}", self.width)));
+                summary.push(format!("sides: {}", sides));
+            }
+        }
+        summary.push(perimeter);
+        if self.kerf > 0.0 {
+            let (toolpath, exact) = self.toolpath_perimeter();
+            summary.push(format!("kerf: {:.3}", self.kerf));
+            summary.push(format!("toolpath perimeter: {:.3}{}", toolpath, if exact { "" } else { " (approximate)" }));
+        }
+        // the drawn contours only match the body once every feature is placed, so until then they are left to verification
+        let geometry = self.geometry();
+        if geometry.missing.is_empty() {
+            summary.push(format!("drawn perimeter: {:.3}", geometry.perimeter()));
+            summary.push(format!("area: {:.3}", geometry.area()));
+            if let Some((low, high)) = geometry.bounds() {
+                summary.push(format!("bounding box: {:.3} x {:.3}", high.x - low.x, high.y - low.y));
+            }
+        }
+        summary.push(quantity);
+
+        summary
+    }
+}
+
+
+/// A hole that adds to the body's overall perimeter.
+pub struct CircularHole {
+    /// The diameter of the hole.
+    diameter: f64,
+    /// How many holes there are.
+    count: usize,
+}
+impl CircularHole {
+    /// Creates a new hole feature.
+    pub fn new(diameter: f64, count: usize) -> Self { Self { diameter, count } }
+}
+impl Feature for CircularHole {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::CircularHoleFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.diameter, self.diameter))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        Some(FeatureGeometry::Hole(Contour::circle(Point::new(0.0, 0.0), self.diameter / 2.0)))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let diameter = format!("diameter: {}", &format!("{:.3}", self.diameter));
+        dims.push(diameter);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("diameter".to_string(), self.diameter.to_string()),
+        ]
+    }
+
+    /// Every hole is pierced separately.
+    fn pierces(&self) -> usize { self.count }
+
+    /// Gets the perimeter modification of the hole.
+    fn value(&self) -> f64 {
+        formulas::circular_hole_modification(self.diameter) * self.count as f64
+    }
+}
+
+
+
+/// A capsule shaped hole that adds to the body's overall perimeter.
+pub struct CapsularHole {
+    /// The diameter of the hole.
+    diameter: f64,
+    /// The total width of the hole.
+    width: f64,
+    /// How many holes there are.
+    count: usize,
+}
+impl CapsularHole {
+    /// Creates a new hole feature.
+    pub fn new(diameter: f64, width: f64, count: usize) -> Self { Self { diameter, width, count } }
+}
+impl Feature for CapsularHole {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::CapsularHoleFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.width, self.diameter))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let (radius, straight) = (self.diameter / 2.0, (self.width - self.diameter) / 2.0);
+        Some(FeatureGeometry::Hole(Contour::new(vec![
+            Segment::Line { start: Point::new(-straight, -radius), end: Point::new(straight, -radius) },
+            Segment::Arc { center: Point::new(straight, 0.0), radius, start_angle: -PI / 2.0, sweep: PI },
+            Segment::Line { start: Point::new(straight, radius), end: Point::new(-straight, radius) },
+            Segment::Arc { center: Point::new(-straight, 0.0), radius, start_angle: PI / 2.0, sweep: PI },
+        ])))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let diameter = format!("diameter: {}, ", &format!("{:.3}", self.diameter));
+        let width = format!("width: {}", &format!("{:.3}", self.width));
+        dims.push(diameter);
+        dims.push(width);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("diameter".to_string(), self.diameter.to_string()),
+            ("width".to_string(), self.width.to_string()),
+        ]
+    }
+
+    /// Every hole is pierced separately.
+    fn pierces(&self) -> usize { self.count }
+
+    /// Gets the perimeter modification of the hole.
+    fn value(&self) -> f64 {
+        formulas::capsular_hole_modification(self.diameter, self.width) * self.count as f64
+    }
+}
+
+
+
+/// Denotes how a sharp internal corner is relieved for a round cutting tool.
+#[derive(Clone, Copy)]
+pub enum CornerRelief {
+    /// The corner is rounded with a radius.
+    Radius,
+    /// A circle is cut diagonally through the corner.
+    DogBone,
+    /// A circle is cut through the corner along one wall.
+    TBone,
+}
+impl CornerRelief {
+    /// Returns the relief for the value entered in a feature addition path.
+    pub fn from_value(value: f64) -> Self {
+        match value as i32 {
+            1 => CornerRelief::DogBone,
+            2 => CornerRelief::TBone,
+            _ => CornerRelief::Radius,
+        }
+    }
+
+    /// Returns the value entered in a feature addition path for the relief.
+    pub fn value(&self) -> i32 {
+        match self {
+            CornerRelief::Radius => 0,
+            CornerRelief::DogBone => 1,
+            CornerRelief::TBone => 2,
+        }
+    }
+
+    /// Returns the name of the relief.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CornerRelief::Radius => "radius",
+            CornerRelief::DogBone => "dog-bone",
+            CornerRelief::TBone => "t-bone",
+        }
+    }
+
+    /// Returns how far the relief reaches along the wall it runs along.
+    pub fn wall_length(&self, radius: f64) -> f64 {
+        match self {
+            CornerRelief::Radius => radius,
+            CornerRelief::DogBone => 2.0_f64.sqrt() * radius,
+            CornerRelief::TBone => 2.0 * radius,
+        }
+    }
+
+    /// Returns how the relief cuts a corner in the geometry, with a t-bone along the side before or after the corner.
+    pub fn corner_cut(&self, radius: f64, after: bool) -> CornerCut {
+        if radius <= 0.0 { return CornerCut::Sharp; }
+        match self {
+            CornerRelief::Radius => CornerCut::Round(radius),
+            CornerRelief::DogBone => CornerCut::DogBone(radius),
+            CornerRelief::TBone => CornerCut::TBone(radius, after),
+        }
+    }
+}
+
+
+
+/// A rectangular hole that adds to the body's overall perimeter.
+pub struct RectangularHole {
+    /// The width of the hole.
+    width: f64,
+    /// The height of the hole.
+    height: f64,
+    /// The radius of the hole's corners or of their reliefs (zero for sharp corners).
+    corner_radius: f64,
+    /// How the hole's corners are rounded or relieved.
+    corner_style: CornerRelief,
+    /// How many holes there are.
+    count: usize,
+}
+impl RectangularHole {
+    /// Creates a new hole feature.
+    pub fn new(width: f64, height: f64, corner_radius: f64, corner_style: CornerRelief, count: usize) -> Self { Self { width, height, corner_radius, corner_style, count } }
+}
+impl Feature for RectangularHole {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::RectangularHoleFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.width, self.height))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let (half_width, half_height) = (self.width / 2.0, self.height / 2.0);
+        let points = [Point::new(-half_width, -half_height), Point::new(half_width, -half_height), Point::new(half_width, half_height), Point::new(-half_width, half_height)];
+        // t-bones cut along the width, which comes after the first and third corners
+        let cuts: Vec<CornerCut> = (0..4).map(|index| self.corner_style.corner_cut(self.corner_radius, index % 2 == 0)).collect();
+        Some(FeatureGeometry::Hole(Contour::new(geometry::path(&points, &cuts, true))))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let width = format!("width: {}, ", &format!("{:.3}", self.width));
+        let height = format!("height: {}, ", &format!("{:.3}", self.height));
+        let corner_radius = format!("corner radius: {}, ", &format!("{:.3}", self.corner_radius));
+        let corner_style = format!("corner style: {}", self.corner_style.name());
+        dims.push(width);
+        dims.push(height);
+        dims.push(corner_radius);
+        dims.push(corner_style);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("width".to_string(), self.width.to_string()),
+            ("height".to_string(), self.height.to_string()),
+            ("corner radius".to_string(), self.corner_radius.to_string()),
+            ("corner style".to_string(), self.corner_style.value().to_string()),
+        ]
+    }
+
+    /// Every hole is pierced separately.
+    fn pierces(&self) -> usize { self.count }
+
+    /// Gets the perimeter modification of the hole.
+    fn value(&self) -> f64 {
+        formulas::rectangular_hole_modification(self.width, self.height, self.corner_radius, self.corner_style) * self.count as f64
+    }
 }
 
 
-/// A hole that adds to the body's overall perimeter.
-pub struct CircularHole {
-    /// The diameter of the hole.
-    diameter: f64,
+
+/// A regular polygon shaped hole (hex, octagon, etc.) that adds to the body's overall perimeter.
+pub struct PolygonHole {
+    /// How many sides the polygon has.
+    sides: usize,
+    /// The size of the polygon, measured across its flats or across its corners.
+    size: f64,
+    /// Whether the size is measured across the corners instead of across the flats.
+    across_corners: bool,
     /// How many holes there are.
     count: usize,
 }
-impl CircularHole {
+impl PolygonHole {
     /// Creates a new hole feature.
-    pub fn new(diameter: f64, count: usize) -> Self { Self { diameter, count } }
+    pub fn new(sides: usize, size: f64, across_corners: bool, count: usize) -> Self { Self { sides, size, across_corners, count } }
 }
-impl Feature for CircularHole {
+impl Feature for PolygonHole {
     /// The type of the feature.
-    fn shape(&self) -> Features { Features::CircularHoleFeature }
+    fn shape(&self) -> Features { Features::PolygonHoleFeature }
 
     /// Allows type-specific field access
     fn as_any(&self) -> &dyn Any {
@@ -492,38 +2091,151 @@ impl Feature for CircularHole {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        let diameter = if self.across_corners { self.size } else { self.size / (PI / self.sides as f64).cos() };
+        Some((diameter, diameter))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let half_angle = PI / self.sides as f64;
+        let radius = if self.across_corners { self.size / 2.0 } else { self.size / 2.0 / half_angle.cos() };
+        let points: Vec<Point> = (0..self.sides)
+            .map(|index| Point::on_circle(Point::new(0.0, 0.0), radius, (-PI / 2.0) + half_angle + (2.0 * half_angle * index as f64)))
+            .collect();
+        Some(FeatureGeometry::Hole(Contour::polygon(&points)))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
-        let diameter = format!("diameter: {}", format!("{:.3}", self.diameter));
-        dims.push(diameter);
+        let sides = format!("sides: {}, ", self.sides);
+        let size = format!("{}: {}", if self.across_corners { "across corners" } else { "across flats" }, &format!("{:.3}", self.size));
+        dims.push(sides);
+        dims.push(size);
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("sides".to_string(), self.sides.to_string()),
+            ("size".to_string(), self.size.to_string()),
+            ("size type".to_string(), if self.across_corners { "1" } else { "0" }.to_string()),
+        ]
+    }
+
+    /// Every hole is pierced separately.
+    fn pierces(&self) -> usize { self.count }
+
     /// Gets the perimeter modification of the hole.
     fn value(&self) -> f64 {
-        formulas::circular_hole_modification(self.diameter) * self.count as f64
+        formulas::polygon_hole_modification(self.sides, self.size, self.across_corners) * self.count as f64
     }
 }
 
 
 
-/// A capsule shaped hole that adds to the body's overall perimeter.
-pub struct CapsularHole {
-    /// The diameter of the hole.
+/// A keyhole: a large circular hole joined to a narrower slot with a rounded end.
+pub struct Keyhole {
+    /// The diameter of the large circle.
+    large_diameter: f64,
+    /// The width of the slot.
+    slot_width: f64,
+    /// The length from the center of the large circle to the far end of the slot.
+    slot_length: f64,
+    /// How many keyholes there are.
+    count: usize,
+}
+impl Keyhole {
+    /// Creates a new keyhole feature.
+    pub fn new(large_diameter: f64, slot_width: f64, slot_length: f64, count: usize) -> Self { Self { large_diameter, slot_width, slot_length, count } }
+}
+impl Feature for Keyhole {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::KeyholeFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        // the slot can point any way from the large circle's center
+        let reach = (self.large_diameter / 2.0).max(self.slot_length) * 2.0;
+        Some((reach, reach))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        // the slot runs to the right of the large circle's center
+        let (large_radius, slot_radius) = (self.large_diameter / 2.0, self.slot_width / 2.0);
+        let join_angle = (slot_radius / large_radius).asin();
+        let join = (large_radius.powi(2) - slot_radius.powi(2)).sqrt();
+        let end = self.slot_length - slot_radius;
+        Some(FeatureGeometry::Hole(Contour::new(vec![
+            Segment::Arc { center: Point::new(0.0, 0.0), radius: large_radius, start_angle: join_angle, sweep: (2.0 * PI) - (2.0 * join_angle) },
+            Segment::Line { start: Point::new(join, -slot_radius), end: Point::new(end, -slot_radius) },
+            Segment::Arc { center: Point::new(end, 0.0), radius: slot_radius, start_angle: -PI / 2.0, sweep: PI },
+            Segment::Line { start: Point::new(end, slot_radius), end: Point::new(join, slot_radius) },
+        ])))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let large_diameter = format!("large diameter: {}, ", &format!("{:.3}", self.large_diameter));
+        let slot_width = format!("slot width: {}, ", &format!("{:.3}", self.slot_width));
+        let slot_length = format!("slot length: {}", &format!("{:.3}", self.slot_length));
+        dims.push(large_diameter);
+        dims.push(slot_width);
+        dims.push(slot_length);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("large diameter".to_string(), self.large_diameter.to_string()),
+            ("slot width".to_string(), self.slot_width.to_string()),
+            ("slot length".to_string(), self.slot_length.to_string()),
+        ]
+    }
+
+    /// Every hole is pierced separately.
+    fn pierces(&self) -> usize { self.count }
+
+    /// Gets the perimeter modification of the keyhole.
+    fn value(&self) -> f64 {
+        formulas::keyhole_modification(self.large_diameter, self.slot_width, self.slot_length) * self.count as f64
+    }
+}
+
+
+
+/// A circular hole with one flat (D) or two opposite flats (double D) for keyed shafts.
+pub struct DHole {
+    /// The diameter of the circle.
     diameter: f64,
-    /// The total width of the hole.
-    width: f64,
+    /// The distance across the flat to the opposite side, or across both flats.
+    across_flats: f64,
+    /// How many flats there are (1 or 2).
+    flats: usize,
     /// How many holes there are.
     count: usize,
 }
-impl CapsularHole {
+impl DHole {
     /// Creates a new hole feature.
-    pub fn new(diameter: f64, width: f64, count: usize) -> Self { Self { diameter, width, count } }
+    pub fn new(diameter: f64, across_flats: f64, flats: usize, count: usize) -> Self { Self { diameter, across_flats, flats, count } }
 }
-impl Feature for CapsularHole {
+impl Feature for DHole {
     /// The type of the feature.
-    fn shape(&self) -> Features { Features::CapsularHoleFeature }
+    fn shape(&self) -> Features { Features::DHoleFeature }
 
     /// Allows type-specific field access
     fn as_any(&self) -> &dyn Any {
@@ -533,26 +2245,68 @@ impl Feature for CapsularHole {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.diameter, self.diameter))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        // the flat is on the right, and a second flat is on the left
+        let radius = self.diameter / 2.0;
+        let flat_distance = if self.flats == 2 { self.across_flats / 2.0 } else { self.across_flats - radius };
+        let flat_angle = (flat_distance / radius).acos();
+        let half_flat = radius * flat_angle.sin();
+        let center = Point::new(0.0, 0.0);
+        let right_flat = Segment::Line { start: Point::new(flat_distance, -half_flat), end: Point::new(flat_distance, half_flat) };
+        let segments = if self.flats == 2 {
+            vec![
+                Segment::Arc { center, radius, start_angle: flat_angle, sweep: PI - (2.0 * flat_angle) },
+                Segment::Line { start: Point::new(-flat_distance, half_flat), end: Point::new(-flat_distance, -half_flat) },
+                Segment::Arc { center, radius, start_angle: PI + flat_angle, sweep: PI - (2.0 * flat_angle) },
+                right_flat,
+            ]
+        }
+        else {
+            vec![Segment::Arc { center, radius, start_angle: flat_angle, sweep: (2.0 * PI) - (2.0 * flat_angle) }, right_flat]
+        };
+        Some(FeatureGeometry::Hole(Contour::new(segments)))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
         let diameter = format!("diameter: {}, ", &format!("{:.3}", self.diameter));
-        let width = format!("width: {}", &format!("{:.3}", self.width));
+        let across_flats = format!("across flats: {}, ", &format!("{:.3}", self.across_flats));
+        let flats = format!("flats: {}", self.flats);
         dims.push(diameter);
-        dims.push(width);
+        dims.push(across_flats);
+        dims.push(flats);
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("diameter".to_string(), self.diameter.to_string()),
+            ("across flats".to_string(), self.across_flats.to_string()),
+            ("flats".to_string(), self.flats.to_string()),
+        ]
+    }
+
+    /// Every hole is pierced separately.
+    fn pierces(&self) -> usize { self.count }
+
     /// Gets the perimeter modification of the hole.
     fn value(&self) -> f64 {
-        formulas::capsular_hole_modification(self.diameter, self.width) * self.count as f64
+        formulas::d_hole_modification(self.diameter, self.across_flats, self.flats) * self.count as f64
     }
 }
 
 
 
-/// A rectangular hole that adds to the body's overall perimeter.
-pub struct RectangularHole {
+/// An elliptical hole.
+pub struct EllipticalHole {
     /// The width of the hole.
     width: f64,
     /// The height of the hole.
@@ -560,13 +2314,13 @@ pub struct RectangularHole {
     /// How many holes there are.
     count: usize,
 }
-impl RectangularHole {
-    /// Creates a new hole feature.
+impl EllipticalHole {
+    /// Creates a new elliptical hole feature.
     pub fn new(width: f64, height: f64, count: usize) -> Self { Self { width, height, count } }
 }
-impl Feature for RectangularHole {
+impl Feature for EllipticalHole {
     /// The type of the feature.
-    fn shape(&self) -> Features { Features::RectangularHoleFeature }
+    fn shape(&self) -> Features { Features::EllipticalHoleFeature }
 
     /// Allows type-specific field access
     fn as_any(&self) -> &dyn Any {
@@ -576,6 +2330,17 @@ impl Feature for RectangularHole {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.width, self.height))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let points = geometry::ellipse_points(self.width / 2.0, self.height / 2.0, 0.0, 2.0 * PI, geometry::CURVE_SAMPLES);
+        Some(FeatureGeometry::Hole(Contour::new(geometry::polyline(&points))))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
@@ -586,9 +2351,225 @@ impl Feature for RectangularHole {
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("width".to_string(), self.width.to_string()),
+            ("height".to_string(), self.height.to_string()),
+        ]
+    }
+
+    /// Every hole is pierced separately.
+    fn pierces(&self) -> usize { self.count }
+
+    /// Gets the perimeter modification of the hole.
+    fn value(&self) -> f64 {
+        formulas::elliptical_hole_modification(self.width, self.height) * self.count as f64
+    }
+}
+
+
+
+/// A circular or elliptical hole clipped by a straight flat across its width.
+pub struct SegmentHole {
+    /// The width of the full circle or ellipse.
+    width: f64,
+    /// The height of the full circle or ellipse.
+    height: f64,
+    /// The height of the hole that is kept, from the flat to the far side of the curve.
+    segment_height: f64,
+    /// How many holes there are.
+    count: usize,
+}
+impl SegmentHole {
+    /// Creates a new segment hole feature.
+    pub fn new(width: f64, height: f64, segment_height: f64, count: usize) -> Self { Self { width, height, segment_height, count } }
+}
+impl Feature for SegmentHole {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::SegmentHoleFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.width, self.height))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        // the ellipse is centered on the placement and the flat is below the center when less than half is kept
+        let (semi_width, semi_height) = (self.width / 2.0, self.height / 2.0);
+        let flat_angle = ((semi_height - self.segment_height) / semi_height).clamp(-1.0, 1.0).asin();
+        let mut points = geometry::ellipse_points(semi_width, semi_height, flat_angle, PI - flat_angle, geometry::CURVE_SAMPLES);
+        points.push(points[0]);
+        Some(FeatureGeometry::Hole(Contour::new(geometry::polyline(&points))))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let width = format!("width: {}, ", &format!("{:.3}", self.width));
+        let height = format!("height: {}, ", &format!("{:.3}", self.height));
+        let segment_height = format!("segment height: {}", &format!("{:.3}", self.segment_height));
+        dims.push(width);
+        dims.push(height);
+        dims.push(segment_height);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("width".to_string(), self.width.to_string()),
+            ("height".to_string(), self.height.to_string()),
+            ("segment height".to_string(), self.segment_height.to_string()),
+        ]
+    }
+
+    /// Every hole is pierced separately.
+    fn pierces(&self) -> usize { self.count }
+
     /// Gets the perimeter modification of the hole.
     fn value(&self) -> f64 {
-        formulas::rectangular_hole_modification(self.width, self.height) * self.count as f64
+        formulas::segment_hole_modification(self.width, self.height, self.segment_height) * self.count as f64
+    }
+}
+
+
+
+/// Denotes how the holes of a hole pattern are arranged.
+#[derive(Clone, Copy)]
+pub enum PatternLayout {
+    /// Holes evenly spaced on a pitch circle, with the first hole at the start angle (counter-clockwise from the +x direction).
+    Polar { holes: usize, pitch_circle_diameter: f64, start_angle: f64, center: (f64, f64) },
+    /// Holes in rows and columns, with the first hole at the origin and the rest going up and to the right.
+    Grid { columns: usize, rows: usize, column_pitch: f64, row_pitch: f64, origin: (f64, f64) },
+}
+
+
+
+/// A pattern of circular holes with known positions, measured from the bottom left corner of the body.
+pub struct HolePattern {
+    /// The diameter of every hole.
+    diameter: f64,
+    /// How the holes are arranged.
+    layout: PatternLayout,
+}
+impl HolePattern {
+    /// Creates a new hole pattern feature.
+    pub fn new(diameter: f64, layout: PatternLayout) -> Self { Self { diameter, layout } }
+
+    /// Returns the diameter of every hole.
+    pub fn diameter(&self) -> f64 { self.diameter }
+
+    /// Returns how many holes are in the pattern.
+    pub fn holes(&self) -> usize {
+        match self.layout {
+            PatternLayout::Polar { holes, .. } => { holes }
+            PatternLayout::Grid { columns, rows, .. } => { columns * rows }
+        }
+    }
+
+    /// Returns the center of every hole.
+    pub fn positions(&self) -> Vec<(f64, f64)> {
+        match self.layout {
+            PatternLayout::Polar { holes, pitch_circle_diameter, start_angle, center } => {
+                (0..holes)
+                    .map(|index| {
+                        let angle = start_angle.to_radians() + (2.0 * PI * index as f64 / holes as f64);
+                        (center.0 + (pitch_circle_diameter / 2.0) * angle.cos(), center.1 + (pitch_circle_diameter / 2.0) * angle.sin())
+                    })
+                    .collect()
+            }
+            PatternLayout::Grid { columns, rows, column_pitch, row_pitch, origin } => {
+                (0..rows)
+                    .flat_map(|row| (0..columns).map(move |column| (origin.0 + column as f64 * column_pitch, origin.1 + row as f64 * row_pitch)))
+                    .collect()
+            }
+        }
+    }
+}
+impl Feature for HolePattern {
+    /// The type of the feature.
+    fn shape(&self) -> Features {
+        match self.layout {
+            PatternLayout::Polar { .. } => { Features::PolarHolePatternFeature }
+            PatternLayout::Grid { .. } => { Features::GridHolePatternFeature }
+        }
+    }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// A pattern is always a single entry.
+    fn count(&self) -> usize { 1 }
+
+    /// Every hole in the pattern, at its position.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let radius = self.diameter / 2.0;
+        Some(FeatureGeometry::PositionedHoles(self.positions().into_iter().map(|(x, y)| Contour::circle(Point::new(x, y), radius)).collect()))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let holes = format!("holes: {}, ", self.holes());
+        let diameter = format!("diameter: {}, ", &format!("{:.3}", self.diameter));
+        dims.push(holes);
+        dims.push(diameter);
+        match self.layout {
+            PatternLayout::Polar { pitch_circle_diameter, start_angle, center, .. } => {
+                dims.push(format!("pitch circle diameter: {}, ", &format!("{:.3}", pitch_circle_diameter)));
+                dims.push(format!("start angle: {}, ", &format!("{:.3}", start_angle)));
+                dims.push(format!("center: ({:.3}, {:.3})", center.0, center.1));
+            }
+            PatternLayout::Grid { columns, rows, column_pitch, row_pitch, origin } => {
+                dims.push(format!("grid: {} x {}, ", columns, rows));
+                dims.push(format!("pitch: {} x {}, ", &format!("{:.3}", column_pitch), &format!("{:.3}", row_pitch)));
+                dims.push(format!("origin: ({:.3}, {:.3})", origin.0, origin.1));
+            }
+        }
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        let mut parameters = vec![("hole diameter".to_string(), self.diameter.to_string())];
+        match self.layout {
+            PatternLayout::Polar { holes, pitch_circle_diameter, start_angle, center } => {
+                parameters.push(("holes".to_string(), holes.to_string()));
+                parameters.push(("pitch circle diameter".to_string(), pitch_circle_diameter.to_string()));
+                parameters.push(("start angle".to_string(), start_angle.to_string()));
+                parameters.push(("center x".to_string(), center.0.to_string()));
+                parameters.push(("center y".to_string(), center.1.to_string()));
+            }
+            PatternLayout::Grid { columns, rows, column_pitch, row_pitch, origin } => {
+                parameters.push(("columns".to_string(), columns.to_string()));
+                parameters.push(("rows".to_string(), rows.to_string()));
+                parameters.push(("column pitch".to_string(), column_pitch.to_string()));
+                parameters.push(("row pitch".to_string(), row_pitch.to_string()));
+                parameters.push(("origin x".to_string(), origin.0.to_string()));
+                parameters.push(("origin y".to_string(), origin.1.to_string()));
+            }
+        }
+        parameters
+    }
+
+    /// Every hole in the pattern is pierced separately.
+    fn pierces(&self) -> usize { self.holes() }
+
+    /// Gets the perimeter modification of every hole in the pattern.
+    fn value(&self) -> f64 {
+        formulas::circular_hole_modification(self.diameter) * self.holes() as f64
     }
 }
 
@@ -617,6 +2598,11 @@ impl Feature for Fillet {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        Some(FeatureGeometry::Corner(CornerCut::Round(self.radius)))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
@@ -625,6 +2611,13 @@ impl Feature for Fillet {
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("radius".to_string(), self.radius.to_string()),
+        ]
+    }
+
     /// Gets the perimeter modification of the fillet.
     fn value(&self) -> f64 {
         formulas::fillet_modification(self.radius) * self.count as f64
@@ -633,16 +2626,23 @@ impl Feature for Fillet {
 
 
 
-/// A simple chamfer.
+/// A simple chamfer, called out as two legs (5 x 10) or a leg and an angle (5 x 30°).
 pub struct Chamfer {
-    /// The size of the fillet (equal along the width and height of the body).
-    size: f64,
-    /// How many fillets there are.
+    /// The length the chamfer cuts back along the first edge.
+    leg: f64,
+    /// The length the chamfer cuts back along the second edge.
+    second_leg: f64,
+    /// How many chamfers there are.
     count: usize,
 }
 impl Chamfer {
-    /// Creates a new fillet feature.
-    pub fn new(size: f64, count: usize) -> Self { Self { size, count } }
+    /// Creates a new chamfer feature from two legs.
+    pub fn new(leg: f64, second_leg: f64, count: usize) -> Self { Self { leg, second_leg, count } }
+
+    /// Creates a new chamfer feature from a leg and the angle the chamfer makes with the leg's edge.
+    pub fn from_angle(leg: f64, angle: f64, count: usize) -> Self {
+        Self::new(leg, leg * angle.to_radians().tan(), count)
+    }
 }
 impl Feature for Chamfer {
     /// The name of the feature.
@@ -656,17 +2656,82 @@ impl Feature for Chamfer {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        Some(FeatureGeometry::Corner(CornerCut::Chamfer(self.leg, self.second_leg)))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
-        let size = format!("size: {}", &format!("{:.3}", self.size));
-        dims.push(size);
+        let legs = format!("legs: {} x {}", &format!("{:.3}", self.leg), &format!("{:.3}", self.second_leg));
+        dims.push(legs);
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("leg".to_string(), self.leg.to_string()),
+            ("second size".to_string(), self.second_leg.to_string()),
+            ("second size type".to_string(), "0".to_string()),
+        ]
+    }
+
     /// Gets the perimeter modification of the chamfer.
     fn value(&self) -> f64 {
-        formulas::chamfer_modification(self.size) * self.count as f64
+        formulas::chamfer_modification(self.leg, self.second_leg) * self.count as f64
+    }
+}
+
+
+
+/// A relieved internal corner, such as the inside corner of a notch or claw cut with a round tool.
+pub struct InternalCorner {
+    /// The radius of the corner or of the tool cutting the relief.
+    radius: f64,
+    /// How the corner is relieved.
+    relief: CornerRelief,
+    /// How many internal corners there are.
+    count: usize,
+}
+impl InternalCorner {
+    /// Creates a new internal corner feature.
+    pub fn new(radius: f64, relief: CornerRelief, count: usize) -> Self { Self { radius, relief, count } }
+}
+impl Feature for InternalCorner {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::InternalCornerFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let radius = format!("radius: {}, ", &format!("{:.3}", self.radius));
+        let relief = format!("relief style: {}", self.relief.name());
+        dims.push(radius);
+        dims.push(relief);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("radius".to_string(), self.radius.to_string()),
+            ("relief style".to_string(), self.relief.value().to_string()),
+        ]
+    }
+
+    /// Gets the perimeter modification of the internal corner.
+    fn value(&self) -> f64 {
+        formulas::corner_relief_modification(self.radius, self.relief) * self.count as f64
     }
 }
 
@@ -697,6 +2762,18 @@ impl Feature for Slope {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.height / self.angle.to_radians().tan(), self.height))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        // the edge steps into the body by the height for the rest of its length
+        let run = self.height / self.angle.to_radians().tan();
+        Some(FeatureGeometry::Edge(EdgeProfile::step(geometry::polyline(&[Point::new(0.0, 0.0), Point::new(run, self.height)]), self.height)))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
@@ -707,6 +2784,14 @@ impl Feature for Slope {
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("height".to_string(), self.height.to_string()),
+            ("angle".to_string(), self.angle.to_string()),
+        ]
+    }
+
     /// Gets the perimeter modification of the slope.
     fn value(&self) -> f64 {
         formulas::slope_modification(self.height, self.angle) * self.count as f64
@@ -740,6 +2825,19 @@ impl Feature for Cliff {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.height / self.angle.to_radians().tan(), self.height))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        // the edge steps into the body by the height, with the upper edge overhanging the sloped side
+        let run = self.height / self.angle.to_radians().tan();
+        let points = [Point::new(0.0, 0.0), Point::new(run, 0.0), Point::new(0.0, self.height)];
+        Some(FeatureGeometry::Edge(EdgeProfile::step(geometry::polyline(&points), self.height)))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
@@ -750,24 +2848,80 @@ impl Feature for Cliff {
         dims
     }
 
-    /// Gets the perimeter modification of the cliff.
-    fn value(&self) -> f64 {
-        formulas::cliff_modification(self.height, self.angle) * self.count as f64
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("height".to_string(), self.height.to_string()),
+            ("angle".to_string(), self.angle.to_string()),
+        ]
+    }
+
+    /// Gets the perimeter modification of the cliff.
+    fn value(&self) -> f64 {
+        formulas::cliff_modification(self.height, self.angle) * self.count as f64
+    }
+}
+
+
+
+/// Denotes the shape of the bottom of a notch.
+#[derive(Clone, Copy)]
+pub enum NotchBottom {
+    /// The walls meet a straight bottom.
+    Flat,
+    /// A single arc tangent to both walls (a U bottom).
+    FullRound,
+}
+impl NotchBottom {
+    /// Returns the bottom for the value entered in a feature addition path.
+    pub fn from_value(value: f64) -> Self {
+        match value as i32 {
+            1 => NotchBottom::FullRound,
+            _ => NotchBottom::Flat,
+        }
+    }
+
+    /// Returns the value entered in a feature addition path for the bottom.
+    pub fn value(&self) -> i32 {
+        match self {
+            NotchBottom::Flat => 0,
+            NotchBottom::FullRound => 1,
+        }
+    }
+
+    /// Returns the name of the bottom.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NotchBottom::Flat => "flat",
+            NotchBottom::FullRound => "full round",
+        }
     }
 }
 
 
 
-/// A rectangular cutout.
+/// A cutout into an edge, from a square slot to a V or dovetail.
 pub struct Notch {
+    /// The width of the notch at the edge.
+    width: f64,
     /// The depth of the notch.
     depth: f64,
+    /// The angle of each wall from square, in degrees (positive opens the notch toward the edge, negative undercuts it like a dovetail).
+    wall_angle: f64,
+    /// The shape of the bottom of the notch.
+    bottom: NotchBottom,
+    /// The radius of the reliefs in the notch's two bottom corners (zero for sharp corners).
+    relief_radius: f64,
+    /// How the notch's bottom corners are relieved.
+    relief: CornerRelief,
     /// How many notches there are.
     count: usize,
 }
 impl Notch {
     /// Creates a new notch feature.
-    pub fn new(depth: f64, count: usize) -> Self { Notch { depth, count } }
+    pub fn new(width: f64, depth: f64, wall_angle: f64, bottom: NotchBottom, relief_radius: f64, relief: CornerRelief, count: usize) -> Self {
+        Notch { width, depth, wall_angle, bottom, relief_radius, relief, count }
+    }
 }
 impl Feature for Notch {
     /// The type of the feature.
@@ -781,17 +2935,72 @@ impl Feature for Notch {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.width, self.depth))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let inset = self.depth * self.wall_angle.to_radians().tan();
+        let points = [Point::new(0.0, 0.0), Point::new(inset, self.depth), Point::new(self.width - inset, self.depth), Point::new(self.width, 0.0)];
+        // t-bones cut along the bottom
+        let cuts = match self.bottom {
+            NotchBottom::Flat => {
+                if formulas::notch_bottom_width(self.width, self.depth, self.wall_angle) <= 0.0 {
+                    let points = [points[0], Point::new(self.width / 2.0, self.depth), points[3]];
+                    return Some(FeatureGeometry::Edge(EdgeProfile::new(geometry::polyline(&points))));
+                }
+                [CornerCut::Sharp, self.relief.corner_cut(self.relief_radius, true), self.relief.corner_cut(self.relief_radius, false), CornerCut::Sharp]
+            }
+            NotchBottom::FullRound => {
+                let radius = formulas::full_round_notch_radius(self.width, self.depth, self.wall_angle);
+                [CornerCut::Sharp, CornerCut::Round(radius), CornerCut::Round(radius), CornerCut::Sharp]
+            }
+        };
+        Some(FeatureGeometry::Edge(EdgeProfile::new(geometry::path(&points, &cuts, false))))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
-        let depth = format!("depth: {}", &format!("{:.3}", self.depth));
+        let width = format!("width: {}, ", &format!("{:.3}", self.width));
+        let depth = format!("depth: {}, ", &format!("{:.3}", self.depth));
+        let wall_angle = format!("wall angle: {}, ", &format!("{:.3}", self.wall_angle));
+        dims.push(width);
         dims.push(depth);
+        dims.push(wall_angle);
+        match self.bottom {
+            NotchBottom::Flat => {
+                let bottom = format!("bottom style: {}, ", self.bottom.name());
+                let relief_radius = format!("relief radius: {}, ", &format!("{:.3}", self.relief_radius));
+                let relief = format!("relief style: {}", self.relief.name());
+                dims.push(bottom);
+                dims.push(relief_radius);
+                dims.push(relief);
+            }
+            NotchBottom::FullRound => {
+                dims.push(format!("bottom style: {}", self.bottom.name()));
+            }
+        }
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("width".to_string(), self.width.to_string()),
+            ("depth".to_string(), self.depth.to_string()),
+            ("wall angle".to_string(), self.wall_angle.to_string()),
+            ("bottom style".to_string(), self.bottom.value().to_string()),
+            ("relief radius".to_string(), self.relief_radius.to_string()),
+            ("relief style".to_string(), self.relief.value().to_string()),
+        ]
+    }
+
     /// Gets the perimeter modification of the notch.
     fn value(&self) -> f64 {
-        formulas::notch_modification(self.depth) * self.count as f64
+        formulas::notch_modification(self.width, self.depth, self.wall_angle, self.bottom, self.relief_radius, self.relief) * self.count as f64
     }
 }
 
@@ -822,6 +3031,18 @@ impl Feature for Sawtooth {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.height / self.angle.to_radians().tan(), self.height))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let run = self.height / self.angle.to_radians().tan();
+        let points = [Point::new(0.0, 0.0), Point::new(run, self.height), Point::new(run, 0.0)];
+        Some(FeatureGeometry::Edge(EdgeProfile::new(geometry::polyline(&points))))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
@@ -832,6 +3053,14 @@ impl Feature for Sawtooth {
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("height".to_string(), self.height.to_string()),
+            ("angle".to_string(), self.angle.to_string()),
+        ]
+    }
+
     /// Gets the perimeter modification of the valley.
     fn value(&self) -> f64 {
         formulas::sawtooth_modification(self.height, self.angle) * self.count as f64
@@ -865,6 +3094,19 @@ impl Feature for Claw {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((2.0 * self.height / self.angle.to_radians().tan(), self.height))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        // a square wall, a bottom as long as twice the run and a sloped wall undercutting the edge
+        let run = self.height / self.angle.to_radians().tan();
+        let points = [Point::new(0.0, 0.0), Point::new(0.0, self.height), Point::new(2.0 * run, self.height), Point::new(run, 0.0)];
+        Some(FeatureGeometry::Edge(EdgeProfile::new(geometry::polyline(&points))))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
@@ -875,6 +3117,14 @@ impl Feature for Claw {
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("height".to_string(), self.height.to_string()),
+            ("angle".to_string(), self.angle.to_string()),
+        ]
+    }
+
     /// Gets the perimeter modification of the valley.
     fn value(&self) -> f64 {
         formulas::claw_modification(self.height, self.angle) * self.count as f64
@@ -921,61 +3171,496 @@ impl CompositeSlopeIndex {
         }
     }
 
-    pub fn get_height_differences(&self) -> f64 {
-        self.trackers.iter().map(|tracker| tracker.get_height_difference()).sum()
+    pub fn get_height_differences(&self) -> f64 {
+        self.trackers.iter().map(|tracker| tracker.get_height_difference()).sum()
+    }
+}
+
+
+/// A height tracker for a single collection of composite slopes.
+pub struct CompositeSlopeHeightTracker {
+    /// The id of the collection of composite slopes.
+    slope_id: usize,
+    /// The difference in height from the series of composite slopes.
+    height_tracker: f64,
+}
+impl CompositeSlopeHeightTracker {
+    /// Creates a new height tracker for a collection of composite slopes.
+    pub fn new(slope_id: usize) -> Self { Self { slope_id, height_tracker: 0.0 } }
+
+    /// Adds a height to the height tracker, keeping track of the direction of the slope.
+    pub fn add_height(&mut self, height: f64, slope_direction: SlopeDirection) {
+        match slope_direction {
+            SlopeDirection::Up => self.height_tracker -= height,
+            SlopeDirection::Down => self.height_tracker += height,
+        }
+    }
+
+    /// Returns the difference in height from the last height tracker.
+    pub fn get_height_difference(&self) -> f64 { self.height_tracker.abs() }
+}
+
+
+
+/// A cutout that is a collection of individual composite slopes.
+/// These slopes are groups by id's with each id being meant to designate an entire complex slope or valley.
+pub struct CompositeSlope {
+    /// The height of the composite slope (only current section).
+    height: f64,
+    /// The angle of the sloped side of the composite slope.
+    angle: f64,
+    /// Determined whether the composite slope is convex or concave.
+    slope_type: SlopeType,
+    /// The direction of the composite slope.
+    /// Down goes into the body and Up goes out of the body.
+    slope_direction: SlopeDirection,
+    /// Slope id
+    slope_id: usize,
+    /// How many composite slopes there are.
+    count: usize,
+}
+impl CompositeSlope {
+    /// Creates a new composite slope feature.
+    pub fn new(height: f64, angle: f64, slope_type: SlopeType, slope_direction: SlopeDirection, slope_id: usize, count: usize) -> Self { Self { height, angle, slope_type, slope_direction, slope_id, count } }
+}
+impl Feature for CompositeSlope {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::CompositeSlopeFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.height / self.angle.to_radians().tan(), self.height))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        // each composite slope steps the edge, and the steps in a group make up its slopes and valleys
+        let run = self.height / self.angle.to_radians().tan();
+        let rise = match self.slope_direction {
+            SlopeDirection::Down => { self.height }
+            SlopeDirection::Up => { -self.height }
+        };
+        let points = match self.slope_type {
+            SlopeType::Convex => { vec![Point::new(0.0, 0.0), Point::new(run, rise)] }
+            SlopeType::Concave => { vec![Point::new(0.0, 0.0), Point::new(run, 0.0), Point::new(0.0, rise)] }
+        };
+        Some(FeatureGeometry::Edge(EdgeProfile::step(geometry::polyline(&points), rise)))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let height = format!("height: {}, ", &format!("{:.3}", self.height));
+        let angle = format!("angle: {}, ", &format!("{:.3}", self.angle));
+        let slope_type = format!("slope_type: {}, ", match self.slope_type { SlopeType::Convex => "convex", SlopeType::Concave => "concave" });
+        let slope_direction = format!("slope_direction: {}, ", match self.slope_direction { SlopeDirection::Up => "up", SlopeDirection::Down => "down" });
+        let slope_id = format!("slope_id: {}", self.slope_id);
+        dims.push(height);
+        dims.push(angle);
+        dims.push(slope_type);
+        dims.push(slope_direction);
+        dims.push(slope_id);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("height".to_string(), self.height.to_string()),
+            ("angle".to_string(), self.angle.to_string()),
+            ("slope type".to_string(), match self.slope_type { SlopeType::Convex => "0", SlopeType::Concave => "1" }.to_string()),
+            ("slope direction".to_string(), match self.slope_direction { SlopeDirection::Up => "0", SlopeDirection::Down => "1" }.to_string()),
+            ("slope id".to_string(), self.slope_id.to_string()),
+        ]
+    }
+
+    /// Gets the perimeter modification of the valley.
+    fn value(&self) -> f64 {
+        formulas::composite_slope_modification(self.height, self.angle, self.slope_type) * self.count as f64
+    }
+}
+
+
+
+/// An open-ended slot (U-slot) that enters the body from an edge.
+pub struct EdgeSlot {
+    /// The width of the slot.
+    width: f64,
+    /// The depth of the slot from the edge, including any rounded end.
+    depth: f64,
+    /// Whether the end of the slot is a full radius instead of flat.
+    full_radius: bool,
+    /// How many slots there are.
+    count: usize,
+}
+impl EdgeSlot {
+    /// Creates a new edge slot feature.
+    pub fn new(width: f64, depth: f64, full_radius: bool, count: usize) -> Self { Self { width, depth, full_radius, count } }
+}
+impl Feature for EdgeSlot {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::EdgeSlotFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.width, self.depth))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let points = [Point::new(0.0, 0.0), Point::new(0.0, self.depth), Point::new(self.width, self.depth), Point::new(self.width, 0.0)];
+        let end = if self.full_radius { CornerCut::Round(self.width / 2.0) } else { CornerCut::Sharp };
+        Some(FeatureGeometry::Edge(EdgeProfile::new(geometry::path(&points, &[CornerCut::Sharp, end, end, CornerCut::Sharp], false))))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let width = format!("width: {}, ", &format!("{:.3}", self.width));
+        let depth = format!("depth: {}, ", &format!("{:.3}", self.depth));
+        let end = format!("end: {}", if self.full_radius { "full radius" } else { "flat" });
+        dims.push(width);
+        dims.push(depth);
+        dims.push(end);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("width".to_string(), self.width.to_string()),
+            ("depth".to_string(), self.depth.to_string()),
+            ("full radius end".to_string(), if self.full_radius { "1" } else { "0" }.to_string()),
+        ]
+    }
+
+    /// Gets the perimeter modification of the edge slot.
+    fn value(&self) -> f64 {
+        formulas::edge_slot_modification(self.width, self.depth, self.full_radius) * self.count as f64
+    }
+}
+
+
+
+/// A dovetail slot that enters the body from an edge and widens toward its bottom.
+pub struct Dovetail {
+    /// The depth of the dovetail from the edge.
+    depth: f64,
+    /// The width of the dovetail where it opens at the edge.
+    opening_width: f64,
+    /// The angle between each wall and the bottom of the dovetail, in degrees.
+    angle: f64,
+    /// How many dovetails there are.
+    count: usize,
+}
+impl Dovetail {
+    /// Creates a new dovetail feature.
+    pub fn new(depth: f64, opening_width: f64, angle: f64, count: usize) -> Self { Self { depth, opening_width, angle, count } }
+}
+impl Feature for Dovetail {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::DovetailFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.opening_width + (2.0 * self.depth / self.angle.to_radians().tan()), self.depth))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let undercut = self.depth / self.angle.to_radians().tan();
+        let points = [
+            Point::new(undercut, 0.0),
+            Point::new(0.0, self.depth),
+            Point::new(self.opening_width + (2.0 * undercut), self.depth),
+            Point::new(self.opening_width + undercut, 0.0),
+        ];
+        Some(FeatureGeometry::Edge(EdgeProfile::new(geometry::polyline(&points))))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let depth = format!("depth: {}, ", &format!("{:.3}", self.depth));
+        let opening_width = format!("opening width: {}, ", &format!("{:.3}", self.opening_width));
+        let angle = format!("angle: {}", &format!("{:.3}", self.angle));
+        dims.push(depth);
+        dims.push(opening_width);
+        dims.push(angle);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("depth".to_string(), self.depth.to_string()),
+            ("opening width".to_string(), self.opening_width.to_string()),
+            ("angle".to_string(), self.angle.to_string()),
+        ]
+    }
+
+    /// Gets the perimeter modification of the dovetail.
+    fn value(&self) -> f64 {
+        formulas::dovetail_modification(self.depth, self.angle) * self.count as f64
+    }
+}
+
+
+
+/// A T-slot that enters the body from an edge through a narrow neck into a wider head.
+pub struct TSlot {
+    /// The width of the neck at the edge.
+    neck_width: f64,
+    /// The depth of the neck from the edge to the head.
+    neck_depth: f64,
+    /// The width of the head.
+    head_width: f64,
+    /// The depth of the head.
+    head_depth: f64,
+    /// How many t-slots there are.
+    count: usize,
+}
+impl TSlot {
+    /// Creates a new t-slot feature.
+    pub fn new(neck_width: f64, neck_depth: f64, head_width: f64, head_depth: f64, count: usize) -> Self {
+        Self { neck_width, neck_depth, head_width, head_depth, count }
+    }
+}
+impl Feature for TSlot {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::TSlotFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.head_width, self.neck_depth + self.head_depth))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let ledge = (self.head_width - self.neck_width) / 2.0;
+        let (neck, bottom) = (self.neck_depth, self.neck_depth + self.head_depth);
+        let points = [
+            Point::new(ledge, 0.0),
+            Point::new(ledge, neck),
+            Point::new(0.0, neck),
+            Point::new(0.0, bottom),
+            Point::new(self.head_width, bottom),
+            Point::new(self.head_width, neck),
+            Point::new(ledge + self.neck_width, neck),
+            Point::new(ledge + self.neck_width, 0.0),
+        ];
+        Some(FeatureGeometry::Edge(EdgeProfile::new(geometry::polyline(&points))))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let neck = format!("neck: {} x {}, ", &format!("{:.3}", self.neck_width), &format!("{:.3}", self.neck_depth));
+        let head = format!("head: {} x {}", &format!("{:.3}", self.head_width), &format!("{:.3}", self.head_depth));
+        dims.push(neck);
+        dims.push(head);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("neck width".to_string(), self.neck_width.to_string()),
+            ("neck depth".to_string(), self.neck_depth.to_string()),
+            ("head width".to_string(), self.head_width.to_string()),
+            ("head depth".to_string(), self.head_depth.to_string()),
+        ]
+    }
+
+    /// Gets the perimeter modification of the t-slot.
+    fn value(&self) -> f64 {
+        formulas::t_slot_modification(self.neck_width, self.neck_depth, self.head_width, self.head_depth) * self.count as f64
+    }
+}
+
+
+
+/// A free-form edge profile traced through a list of points.
+pub struct FreeFormEdge {
+    /// The points along the profile, as (x, y) with x along the edge and y across it.
+    points: Vec<(f64, f64)>,
+    /// Whether the profile is a smooth curve through the points instead of straight lines between them.
+    smoothed: bool,
+    /// How many of these edges there are.
+    count: usize,
+}
+impl FreeFormEdge {
+    /// Creates a new free-form edge feature.
+    pub fn new(points: Vec<(f64, f64)>, smoothed: bool, count: usize) -> Self { Self { points, smoothed, count } }
+
+    /// Reads a list of points written as "x,y x,y x,y".
+    pub fn parse_points(text: &str) -> Result<Vec<(f64, f64)>, String> {
+        let mut points = Vec::new();
+        for pair in text.split_whitespace() {
+            let (x, y) = pair.split_once(',').ok_or(format!("\"{}\" is not a point written as x,y", pair))?;
+            let x = x.parse::<f64>().map_err(|_| format!("\"{}\" is not a number", x))?;
+            let y = y.parse::<f64>().map_err(|_| format!("\"{}\" is not a number", y))?;
+            points.push((x, y));
+        }
+        Ok(points)
+    }
+
+    /// Writes the points the way they are read.
+    fn points_text(&self) -> String {
+        self.points.iter().map(|(x, y)| format!("{},{}", x, y)).collect::<Vec<String>>().join(" ")
+    }
+}
+impl Feature for FreeFormEdge {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::FreeFormEdgeFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((formulas::chord_length(&self.points), self.points.iter().map(|(_, y)| y.abs()).fold(0.0, f64::max)))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        // y is measured out of the body, and a profile that ends off its starting line steps the rest of the edge
+        let first = *self.points.first()?;
+        let curve = if self.smoothed { formulas::catmull_rom_points(&self.points) } else { self.points.clone() };
+        let points: Vec<Point> = curve.iter().map(|(x, y)| Point::new(x - first.0, first.1 - y)).collect();
+        let level = points.last().map(|point| point.y).unwrap_or(0.0);
+        Some(FeatureGeometry::Edge(EdgeProfile::step(geometry::polyline(&points), level)))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let points = format!("points: {}, ", self.points.len());
+        let span = format!("span: {}, ", &format!("{:.3}", formulas::chord_length(&self.points)));
+        let smoothing = format!("smoothing: {}", if self.smoothed { "smooth" } else { "straight" });
+        dims.push(points);
+        dims.push(span);
+        dims.push(smoothing);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("points".to_string(), self.points_text()),
+            ("smoothing".to_string(), if self.smoothed { "1" } else { "0" }.to_string()),
+        ]
+    }
+
+    /// Gets the perimeter modification of the free-form edge.
+    fn value(&self) -> f64 {
+        formulas::free_form_edge_modification(&self.points, self.smoothed) * self.count as f64
     }
 }
 
 
-/// A height tracker for a single collection of composite slopes.
-pub struct CompositeSlopeHeightTracker {
-    /// The id of the collection of composite slopes.
-    slope_id: usize,
-    /// The difference in height from the series of composite slopes.
-    height_tracker: f64,
+
+/// Denotes the repeating profile of a periodic edge.
+#[derive(Clone, Copy)]
+pub enum PeriodicProfile {
+    /// A sinusoidal wave.
+    Wave,
+    /// A zig-zag of straight teeth.
+    Serration,
+    /// A row of circular arcs meeting in points.
+    Scallops,
 }
-impl CompositeSlopeHeightTracker {
-    /// Creates a new height tracker for a collection of composite slopes.
-    pub fn new(slope_id: usize) -> Self { Self { slope_id, height_tracker: 0.0 } }
+impl PeriodicProfile {
+    /// Returns the profile for the value entered in a feature addition path.
+    pub fn from_value(value: f64) -> Self {
+        match value as i32 {
+            1 => PeriodicProfile::Serration,
+            2 => PeriodicProfile::Scallops,
+            _ => PeriodicProfile::Wave,
+        }
+    }
 
-    /// Adds a height to the height tracker, keeping track of the direction of the slope.
-    pub fn add_height(&mut self, height: f64, slope_direction: SlopeDirection) {
-        match slope_direction {
-            SlopeDirection::Up => self.height_tracker -= height,
-            SlopeDirection::Down => self.height_tracker += height,
+    /// Returns the value entered in a feature addition path for the profile.
+    pub fn value(&self) -> i32 {
+        match self {
+            PeriodicProfile::Wave => 0,
+            PeriodicProfile::Serration => 1,
+            PeriodicProfile::Scallops => 2,
         }
     }
 
-    /// Returns the difference in height from the last height tracker.
-    pub fn get_height_difference(&self) -> f64 { self.height_tracker.abs() }
+    /// Returns the name of the profile.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PeriodicProfile::Wave => "wave",
+            PeriodicProfile::Serration => "serrated",
+            PeriodicProfile::Scallops => "scalloped",
+        }
+    }
 }
 
 
 
-/// A cutout that is a collection of individual composite slopes.
-/// These slopes are groups by id's with each id being meant to designate an entire complex slope or valley.
-pub struct CompositeSlope {
-    /// The height of the composite slope (only current section).
-    height: f64,
-    /// The angle of the sloped side of the composite slope.
-    angle: f64,
-    /// Determined whether the composite slope is convex or concave.
-    slope_type: SlopeType,
-    /// The direction of the composite slope.
-    /// Down goes into the body and Up goes out of the body.
-    slope_direction: SlopeDirection,
-    /// Slope id
-    slope_id: usize,
-    /// How many composite slopes there are.
+/// A decorative or grip edge that repeats one profile along part of an edge.
+/// The profile swings the amplitude to either side of the straight edge it replaces.
+pub struct PeriodicEdge {
+    /// The repeating profile.
+    profile: PeriodicProfile,
+    /// The length of the straight edge the profile replaces.
+    edge_length: f64,
+    /// The length of one repeat of the profile.
+    pitch: f64,
+    /// How far the profile reaches to either side of the edge.
+    amplitude: f64,
+    /// How many of these edges there are.
     count: usize,
 }
-impl CompositeSlope {
-    /// Creates a new composite slope feature.
-    pub fn new(height: f64, angle: f64, slope_type: SlopeType, slope_direction: SlopeDirection, slope_id: usize, count: usize) -> Self { Self { height, angle, slope_type, slope_direction, slope_id, count } }
+impl PeriodicEdge {
+    /// Creates a new periodic edge feature.
+    pub fn new(profile: PeriodicProfile, edge_length: f64, pitch: f64, amplitude: f64, count: usize) -> Self {
+        Self { profile, edge_length, pitch, amplitude, count }
+    }
 }
-impl Feature for CompositeSlope {
+impl Feature for PeriodicEdge {
     /// The type of the feature.
-    fn shape(&self) -> Features { Features::CompositeSlopeFeature }
+    fn shape(&self) -> Features { Features::PeriodicEdgeFeature }
 
     /// Allows type-specific field access
     fn as_any(&self) -> &dyn Any {
@@ -985,25 +3670,81 @@ impl Feature for CompositeSlope {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.edge_length, 2.0 * self.amplitude))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let repeats = (self.edge_length / self.pitch).round() as usize;
+        let (pitch, amplitude) = (self.pitch, self.amplitude);
+        let segments = match self.profile {
+            PeriodicProfile::Wave => {
+                let samples = repeats * geometry::CURVE_SAMPLES;
+                let points: Vec<Point> = (0..=samples)
+                    .map(|sample| {
+                        let along = self.edge_length * sample as f64 / samples as f64;
+                        Point::new(along, amplitude * (2.0 * PI * along / pitch).sin())
+                    })
+                    .collect();
+                geometry::polyline(&points)
+            }
+            PeriodicProfile::Serration => {
+                let mut points = vec![Point::new(0.0, 0.0)];
+                for repeat in 0..repeats {
+                    let start = repeat as f64 * pitch;
+                    points.extend([Point::new(start + (pitch / 4.0), amplitude), Point::new(start + (3.0 * pitch / 4.0), -amplitude), Point::new(start + pitch, 0.0)]);
+                }
+                geometry::polyline(&points)
+            }
+            PeriodicProfile::Scallops => {
+                // each scallop dips twice the amplitude into the body
+                (0..repeats)
+                    .map(|repeat| {
+                        let start = repeat as f64 * pitch;
+                        if amplitude <= 0.0 { return Segment::Line { start: Point::new(start, 0.0), end: Point::new(start + pitch, 0.0) }; }
+                        geometry::arc_through(Point::new(start, 0.0), Point::new(start + (pitch / 2.0), 2.0 * amplitude), Point::new(start + pitch, 0.0))
+                    })
+                    .collect()
+            }
+        };
+        Some(FeatureGeometry::Edge(EdgeProfile::new(segments)))
+    }
+
+    /// A periodic edge fits on one line.
+    fn single_line(&self) -> bool { true }
+
+    /// Names the feature after its profile.
+    fn summarize_feature(&self) -> String {
+        format!("{}x {} edge", self.count, self.profile.name())
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
-        let height = format!("height: {}, ", &format!("{:.3}", self.height));
-        let angle = format!("angle: {}, ", &format!("{:.3}", self.angle));
-        let slope_type = format!("slope_type: {}, ", match self.slope_type { SlopeType::Convex => "convex", SlopeType::Concave => "concave" });
-        let slope_direction = format!("slope_direction: {}, ", match self.slope_direction { SlopeDirection::Up => "up", SlopeDirection::Down => "down" });
-        let slope_id = format!("slope_id: {}", self.slope_id);
-        dims.push(height);
-        dims.push(angle);
-        dims.push(slope_type);
-        dims.push(slope_direction);
-        dims.push(slope_id);
+        let edge_length = format!("edge length: {}, ", &format!("{:.3}", self.edge_length));
+        let pitch = format!("pitch: {}, ", &format!("{:.3}", self.pitch));
+        let amplitude = format!("amplitude: {}", &format!("{:.3}", self.amplitude));
+        dims.push(edge_length);
+        dims.push(pitch);
+        dims.push(amplitude);
         dims
     }
 
-    /// Gets the perimeter modification of the valley.
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("profile".to_string(), self.profile.value().to_string()),
+            ("edge length".to_string(), self.edge_length.to_string()),
+            ("pitch".to_string(), self.pitch.to_string()),
+            ("amplitude".to_string(), self.amplitude.to_string()),
+        ]
+    }
+
+    /// Gets the perimeter modification of the periodic edge.
     fn value(&self) -> f64 {
-        formulas::composite_slope_modification(self.height, self.angle, self.slope_type) * self.count as f64
+        formulas::periodic_edge_modification(self.profile, self.edge_length, self.pitch, self.amplitude) * self.count as f64
     }
 }
 
@@ -1034,6 +3775,18 @@ impl Feature for Arc {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((2.0 * (self.height * ((2.0 * self.radius) - self.height)).sqrt(), self.height))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let width = 2.0 * (self.height * ((2.0 * self.radius) - self.height)).sqrt();
+        let arc = geometry::arc_through(Point::new(0.0, 0.0), Point::new(width / 2.0, self.height), Point::new(width, 0.0));
+        Some(FeatureGeometry::Edge(EdgeProfile::new(vec![arc])))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
@@ -1044,6 +3797,14 @@ impl Feature for Arc {
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("radius".to_string(), self.radius.to_string()),
+            ("height".to_string(), self.height.to_string()),
+        ]
+    }
+
     /// Gets the perimeter modification of the arc.
     fn value(&self) -> f64 {
         formulas::arc_modification(self.radius, self.height) * self.count as f64
@@ -1077,6 +3838,22 @@ impl Feature for Ellipse {
     /// The count of the feature.
     fn count(&self) -> usize { self.count }
 
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some((self.width, self.height))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        // half an ellipse as wide as the width that reaches the height into the body
+        let semi_width = self.width / 2.0;
+        let points: Vec<Point> = geometry::ellipse_points(semi_width, self.height, PI, 0.0, geometry::CURVE_SAMPLES)
+            .iter()
+            .map(|point| Point::new(point.x + semi_width, point.y))
+            .collect();
+        Some(FeatureGeometry::Edge(EdgeProfile::new(geometry::polyline(&points))))
+    }
+
     /// A basic dimension overview.
     fn summarize_dimensions(&self) -> Vec<String> {
         let mut dims = Vec::new();
@@ -1087,6 +3864,14 @@ impl Feature for Ellipse {
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("width".to_string(), self.width.to_string()),
+            ("height".to_string(), self.height.to_string()),
+        ]
+    }
+
     /// Gets the perimeter modification of the ellipse.
     fn value(&self) -> f64 {
         formulas::ellipse_modification(self.width, self.height) * self.count as f64
@@ -1095,6 +3880,229 @@ impl Feature for Ellipse {
 
 
 
+/// A partial elliptical arc that replaces the straight chord between its ends.
+pub struct EllipticalArc {
+    /// The width of the full ellipse.
+    width: f64,
+    /// The height of the full ellipse.
+    height: f64,
+    /// The angle from the ellipse's center to the start of the arc, counterclockwise from the width axis in degrees.
+    start_angle: f64,
+    /// The angle from the ellipse's center to the end of the arc, counterclockwise from the width axis in degrees.
+    end_angle: f64,
+    /// How many elliptical arcs there are.
+    count: usize,
+}
+impl EllipticalArc {
+    /// Creates a new elliptical arc.
+    pub fn new(width: f64, height: f64, start_angle: f64, end_angle: f64, count: usize) -> Self {
+        Self { width, height, start_angle, end_angle, count }
+    }
+}
+impl Feature for EllipticalArc {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::EllipticalArcFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        Some(formulas::elliptical_arc_extent(self.width, self.height, self.start_angle, self.end_angle))
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        // the arc is turned so its chord lies along the edge and it bulges into the body
+        let (semi_width, semi_height) = (self.width / 2.0, self.height / 2.0);
+        let start = formulas::ellipse_parameter(semi_width, semi_height, self.start_angle.to_radians());
+        let end = formulas::ellipse_parameter(semi_width, semi_height, self.end_angle.to_radians());
+        let points = geometry::ellipse_points(semi_width, semi_height, start, end, geometry::CURVE_SAMPLES);
+        let (first, last) = (points[0], points[points.len() - 1]);
+        if first.distance(last) <= 0.0 { return None; }
+        let along = last.sub(first).unit();
+        let local = |point: &Point| {
+            let offset = point.sub(first);
+            Point::new((offset.x * along.x) + (offset.y * along.y), (offset.y * along.x) - (offset.x * along.y))
+        };
+        let mut points: Vec<Point> = points.iter().map(local).collect();
+        let deepest = points.iter().map(|point| point.y).fold(0.0, |deepest: f64, depth| if depth.abs() > deepest.abs() { depth } else { deepest });
+        if deepest < 0.0 { points = points.iter().map(|point| Point::new(point.x, -point.y)).collect(); }
+        Some(FeatureGeometry::Edge(EdgeProfile::new(geometry::polyline(&points))))
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let width = format!("width: {}, ", &format!("{:.3}", self.width));
+        let height = format!("height: {}, ", &format!("{:.3}", self.height));
+        let angles = format!("angles: {} to {}", &format!("{:.3}", self.start_angle), &format!("{:.3}", self.end_angle));
+        dims.push(width);
+        dims.push(height);
+        dims.push(angles);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("width".to_string(), self.width.to_string()),
+            ("height".to_string(), self.height.to_string()),
+            ("start angle".to_string(), self.start_angle.to_string()),
+            ("end angle".to_string(), self.end_angle.to_string()),
+        ]
+    }
+
+    /// Gets the perimeter modification of the elliptical arc.
+    fn value(&self) -> f64 {
+        formulas::elliptical_arc_modification(self.width, self.height, self.start_angle, self.end_angle) * self.count as f64
+    }
+}
+
+
+
+/// A spur gear tooth profile with involute flanks.
+/// An external gear is cut from a circular blank and its outline replaces the body's perimeter.
+/// An internal gear is cut as a hole.
+pub struct Gear {
+    /// The module of the teeth (pitch diameter over tooth count).
+    module: f64,
+    /// How many teeth the gear has.
+    teeth: usize,
+    /// The pressure angle of the teeth, in degrees.
+    pressure_angle: f64,
+    /// Whether the teeth point inward from a hole instead of outward from the body.
+    internal: bool,
+    /// How many gears there are.
+    count: usize,
+}
+impl Gear {
+    /// Creates a new gear feature.
+    pub fn new(module: f64, teeth: usize, pressure_angle: f64, internal: bool, count: usize) -> Self {
+        Self { module, teeth, pressure_angle, internal, count }
+    }
+
+    /// Returns the module for a module, or for a diametral pitch in teeth per unit of pitch diameter.
+    pub fn module_from(value: f64, diametral_pitch: bool) -> f64 {
+        if diametral_pitch { 1.0 / value } else { value }
+    }
+
+    /// Returns whether the gear's outline replaces the body's perimeter.
+    pub fn is_external(&self) -> bool { !self.internal }
+
+    /// Returns the diameter of the circular blank an external gear is cut from.
+    pub fn blank_diameter(&self) -> f64 {
+        formulas::gear_radii(self.module, self.teeth, self.pressure_angle, self.internal).outer * 2.0
+    }
+
+    /// Returns the length of the full tooth outline of one gear.
+    pub fn outline_length(&self) -> f64 {
+        formulas::gear_outline_length(self.module, self.teeth, self.pressure_angle, self.internal)
+    }
+
+    /// Returns the tooth outline around the gear's center, with the flanks sampled the same way as its length.
+    pub fn outline(&self) -> Contour {
+        const SAMPLES: usize = 200;
+        let radii = formulas::gear_radii(self.module, self.teeth, self.pressure_angle, self.internal);
+        let half_angle = |radius: f64| formulas::gear_half_angle(self.module, self.teeth, self.pressure_angle, radius);
+        let center = Point::new(0.0, 0.0);
+
+        // one flank from the inner circle to the outer circle as distances from the tooth's middle
+        let involute_start = radii.base.max(radii.inner);
+        let mut flank = vec![(radii.inner, half_angle(involute_start))];
+        flank.extend((0..=SAMPLES).map(|sample| {
+            let radius = involute_start + ((radii.outer - involute_start) * sample as f64 / SAMPLES as f64);
+            (radius, half_angle(radius))
+        }));
+
+        let mut segments = Vec::new();
+        let pitch_angle = 2.0 * PI / self.teeth as f64;
+        for tooth in 0..self.teeth {
+            let middle = tooth as f64 * pitch_angle;
+            let rising: Vec<Point> = flank.iter().map(|(radius, angle)| Point::on_circle(center, *radius, middle - angle)).collect();
+            let falling: Vec<Point> = flank.iter().rev().map(|(radius, angle)| Point::on_circle(center, *radius, middle + angle)).collect();
+            segments.extend(geometry::polyline(&rising));
+            segments.push(Segment::Arc { center, radius: radii.outer, start_angle: middle - half_angle(radii.outer), sweep: 2.0 * half_angle(radii.outer) });
+            segments.extend(geometry::polyline(&falling));
+            segments.push(Segment::Arc {
+                center,
+                radius: radii.inner,
+                start_angle: middle + half_angle(involute_start),
+                sweep: pitch_angle - (2.0 * half_angle(involute_start)),
+            });
+        }
+        Contour::new(segments)
+    }
+}
+impl Feature for Gear {
+    /// The type of the feature.
+    fn shape(&self) -> Features { Features::GearFeature }
+
+    /// Allows type-specific field access
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The count of the feature.
+    fn count(&self) -> usize { self.count }
+
+    /// The area the feature takes up around its placement.
+    fn extent(&self) -> Option<(f64, f64)> {
+        // only an internal gear is a separate cutout on the body
+        let radii = formulas::gear_radii(self.module, self.teeth, self.pressure_angle, self.internal);
+        if self.internal { Some((radii.outer * 2.0, radii.outer * 2.0)) } else { None }
+    }
+
+    /// The feature's outline.
+    fn geometry(&self) -> Option<FeatureGeometry> {
+        let contour = self.outline();
+        if self.internal { Some(FeatureGeometry::Hole(contour)) } else { Some(FeatureGeometry::Outline(contour)) }
+    }
+
+    /// A basic dimension overview.
+    fn summarize_dimensions(&self) -> Vec<String> {
+        let mut dims = Vec::new();
+        let module = format!("module: {}, ", &format!("{:.3}", self.module));
+        let teeth = format!("teeth: {}, ", self.teeth);
+        let pressure_angle = format!("pressure angle: {}, ", &format!("{:.3}", self.pressure_angle));
+        let kind = format!("type: {}, ", if self.internal { "internal" } else { "external" });
+        let outline = format!("outline: {}", &format!("{:.3}", self.outline_length()));
+        dims.push(module);
+        dims.push(teeth);
+        dims.push(pressure_angle);
+        dims.push(kind);
+        dims.push(outline);
+        dims
+    }
+
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("module or pitch".to_string(), self.module.to_string()),
+            ("pitch type".to_string(), "0".to_string()),
+            ("teeth".to_string(), self.teeth.to_string()),
+            ("pressure angle".to_string(), self.pressure_angle.to_string()),
+            ("internal".to_string(), if self.internal { "1" } else { "0" }.to_string()),
+        ]
+    }
+
+    /// Internal gears are pierced like holes.
+    fn pierces(&self) -> usize { if self.internal { self.count } else { 0 } }
+
+    /// Gets the perimeter modification of the gear.
+    /// An external gear's outline is the body's base perimeter, so it adds nothing here.
+    fn value(&self) -> f64 {
+        if self.internal { self.outline_length() * self.count as f64 } else { 0.0 }
+    }
+}
+
+
+
 /// A catch-all other feature.
 pub struct OtherFeature {
     /// Because this is a general catch-all feature, it is only for manual perimeter modifications.
@@ -1126,6 +4134,13 @@ impl Feature for OtherFeature {
         dims
     }
 
+    /// The parameters of the feature.
+    fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("perimeter modification".to_string(), self.perimeter_modification.to_string()),
+        ]
+    }
+
     /// Gets the perimeter modification of the catch-all feature.
     fn value(&self) -> f64 {
         self.perimeter_modification
@@ -1137,7 +4152,7 @@ impl Feature for OtherFeature {
 /// A collection of perimeter modification formulas for various features.
 pub mod formulas {
     use std::f64::consts::PI;
-    use crate::shapes::SlopeType;
+    use crate::shapes::{CornerRelief, NotchBottom, PeriodicProfile, SlopeType};
 
     /// Calculates the perimeter modification for a circular hole.
     pub fn circular_hole_modification(diameter: f64) -> f64 {
@@ -1150,8 +4165,54 @@ pub mod formulas {
     }
 
     /// Calculates the perimeter modification for a rectangular hole.
-    pub fn rectangular_hole_modification(width: f64, height: f64) -> f64 {
-        (width + height) * 2.0
+    /// With rounded corners this is 2(w+h) - 8r + 2πr, and reliefs change each corner the same way as an internal corner.
+    pub fn rectangular_hole_modification(width: f64, height: f64, corner_radius: f64, corner_style: CornerRelief) -> f64 {
+        ((width + height) * 2.0) + (4.0 * corner_relief_modification(corner_radius, corner_style))
+    }
+
+    /// Calculates the perimeter modification for a regular polygon hole.
+    /// Across the corners each side is size * sin(π/n), across the flats each side is size * tan(π/n).
+    pub fn polygon_hole_modification(sides: usize, size: f64, across_corners: bool) -> f64 {
+        let half_angle = PI / sides as f64;
+        let side = if across_corners { size * half_angle.sin() } else { size * half_angle.tan() };
+        side * sides as f64
+    }
+
+    /// Calculates the perimeter modification for a keyhole.
+    /// The large circle loses the arc where the slot joins it, and the slot adds two sides and a rounded end.
+    pub fn keyhole_modification(large_diameter: f64, slot_width: f64, slot_length: f64) -> f64 {
+        let large_radius = large_diameter / 2.0;
+        let slot_radius = slot_width / 2.0;
+        let large_arc = large_radius * ((2.0 * PI) - (2.0 * (slot_radius / large_radius).asin()));
+        let join = (large_radius.powi(2) - slot_radius.powi(2)).sqrt();
+        let sides = 2.0 * (slot_length - slot_radius - join);
+        large_arc + sides + (PI * slot_radius)
+    }
+
+    /// Calculates the perimeter modification for a D or double D hole.
+    /// Each flat removes the arc it cuts off and adds its chord.
+    pub fn d_hole_modification(diameter: f64, across_flats: f64, flats: usize) -> f64 {
+        let radius = diameter / 2.0;
+        // the distance from the center of the circle to each flat
+        let flat_distance = if flats == 2 { across_flats / 2.0 } else { across_flats - radius };
+        let cut_arc = 2.0 * radius * (flat_distance / radius).acos();
+        let chord = 2.0 * (radius.powi(2) - flat_distance.powi(2)).sqrt();
+        circular_hole_modification(diameter) - ((cut_arc - chord) * flats as f64)
+    }
+
+    /// Calculates the perimeter modification for an elliptical hole.
+    pub fn elliptical_hole_modification(width: f64, height: f64) -> f64 {
+        ellipse_perimeter(width / 2.0, height / 2.0)
+    }
+
+    /// Calculates the perimeter modification for a segment hole.
+    /// The curve runs between the parametric angles where the flat meets the ellipse, and the flat closes it.
+    pub fn segment_hole_modification(width: f64, height: f64, segment_height: f64) -> f64 {
+        let (semi_width, semi_height) = (width / 2.0, height / 2.0);
+        let flat_angle = ((semi_height - segment_height) / semi_height).clamp(-1.0, 1.0).asin();
+        let curve = ellipse_arc_length(semi_width, semi_height, flat_angle, PI - flat_angle);
+        let flat = 2.0 * semi_width * flat_angle.cos();
+        curve + flat
     }
 
 
@@ -1162,8 +4223,21 @@ pub mod formulas {
     }
 
     /// Calculates the perimeter modification for a chamfer.
-    pub fn chamfer_modification(size: f64) -> f64 {
-        slope_modification(size, 45.0)
+    /// The hypotenuse replaces both legs: sqrt(a² + b²) - a - b.
+    pub fn chamfer_modification(leg: f64, second_leg: f64) -> f64 {
+        leg.hypot(second_leg) - leg - second_leg
+    }
+
+    /// Calculates the perimeter modification for relieving a sharp 90 degree internal corner.
+    /// A radius replaces two lengths of the radius with a quarter circle.
+    /// A dog-bone cuts a half circle through the corner, replacing √2·r along each wall.
+    /// A t-bone cuts a half circle centered on one wall, replacing 2·r along that wall.
+    pub fn corner_relief_modification(radius: f64, relief: CornerRelief) -> f64 {
+        match relief {
+            CornerRelief::Radius => { fillet_modification(radius) }
+            CornerRelief::DogBone => { (PI * radius) - (2.0 * 2.0_f64.sqrt() * radius) }
+            CornerRelief::TBone => { (PI * radius) - (2.0 * radius) }
+        }
     }
 
     /// Calculates the perimeter modification for a slope.
@@ -1181,8 +4255,58 @@ pub mod formulas {
 
 
     /// Calculates the perimeter modification for a notch.
-    pub fn notch_modification(depth: f64) -> f64 {
-        depth * 2.0
+    /// A flat bottom adds two walls of d/cos θ and a bottom of w - 2d·tan θ in place of the opening, plus the reliefs in its two bottom corners.
+    /// A full round bottom adds two straight wall lengths and an arc sweeping π - 2θ in place of the opening.
+    pub fn notch_modification(width: f64, depth: f64, wall_angle: f64, bottom: NotchBottom, relief_radius: f64, relief: CornerRelief) -> f64 {
+        let rad_angle = wall_angle.to_radians();
+        match bottom {
+            NotchBottom::Flat => {
+                let walls = 2.0 * depth / rad_angle.cos();
+                let corners = match relief {
+                    CornerRelief::Radius => { 2.0 * notch_corner_radius_modification(relief_radius, wall_angle) }
+                    _ => { 2.0 * corner_relief_modification(relief_radius, relief) }
+                };
+                walls + notch_bottom_width(width, depth, wall_angle) - width + corners
+            }
+            NotchBottom::FullRound => {
+                let radius = full_round_notch_radius(width, depth, wall_angle);
+                let walls = 2.0 * full_round_notch_wall_length(width, depth, wall_angle);
+                walls + (radius * (PI - (2.0 * rad_angle))) - width
+            }
+        }
+    }
+
+    /// Calculates the width of a flat notch bottom before any corner radius.
+    pub fn notch_bottom_width(width: f64, depth: f64, wall_angle: f64) -> f64 {
+        width - (2.0 * depth * wall_angle.to_radians().tan())
+    }
+
+    /// Calculates how far a radius in a flat notch bottom corner reaches along the wall and bottom.
+    /// The corner turns by 90° - θ, so the radius is tangent r·tan((90° - θ)/2) from the corner.
+    pub fn notch_corner_tangent_length(radius: f64, wall_angle: f64) -> f64 {
+        radius * ((90.0 - wall_angle).to_radians() / 2.0).tan()
+    }
+
+    /// Calculates the perimeter modification for a radius in a flat notch bottom corner.
+    /// The arc r·φ replaces two tangent lengths, where φ = 90° - θ is the turn at the corner.
+    pub fn notch_corner_radius_modification(radius: f64, wall_angle: f64) -> f64 {
+        let turn = (90.0 - wall_angle).to_radians();
+        (radius * turn) - (2.0 * notch_corner_tangent_length(radius, wall_angle))
+    }
+
+    /// Calculates the radius of a full round notch bottom tangent to both walls.
+    /// R = ((w/2)·cos θ - d·sin θ) / (1 - sin θ)
+    pub fn full_round_notch_radius(width: f64, depth: f64, wall_angle: f64) -> f64 {
+        let rad_angle = wall_angle.to_radians();
+        (((width / 2.0) * rad_angle.cos()) - (depth * rad_angle.sin())) / (1.0 - rad_angle.sin())
+    }
+
+    /// Calculates the straight length of each wall above a full round notch bottom.
+    /// s = (w/2)·sin θ + (d - R)·cos θ
+    pub fn full_round_notch_wall_length(width: f64, depth: f64, wall_angle: f64) -> f64 {
+        let rad_angle = wall_angle.to_radians();
+        let radius = full_round_notch_radius(width, depth, wall_angle);
+        ((width / 2.0) * rad_angle.sin()) + ((depth - radius) * rad_angle.cos())
     }
 
     /// Calculates the perimeter modification for a sawtooth.
@@ -1204,6 +4328,112 @@ pub mod formulas {
         }
     }
 
+    /// Calculates the perimeter modification for an edge slot.
+    /// The slot's walls and end replace the width of the edge it enters from.
+    pub fn edge_slot_modification(width: f64, depth: f64, full_radius: bool) -> f64 {
+        if full_radius {
+            let radius = width / 2.0;
+            (2.0 * (depth - radius)) + (PI * radius) - width
+        }
+        else {
+            depth * 2.0
+        }
+    }
+
+    /// Calculates the perimeter modification for a dovetail.
+    /// The walls add 2d/sin α and the bottom is wider than the opening by 2d/tan α.
+    pub fn dovetail_modification(depth: f64, angle: f64) -> f64 {
+        let rad_angle = angle.to_radians();
+        (2.0 * depth / rad_angle.sin()) + (2.0 * depth / rad_angle.tan())
+    }
+
+    /// Calculates the perimeter modification for a t-slot.
+    /// The neck walls, the head's sides and bottom, and the two ledges either side of the neck replace the neck's opening.
+    pub fn t_slot_modification(neck_width: f64, neck_depth: f64, head_width: f64, head_depth: f64) -> f64 {
+        (2.0 * neck_depth) + (2.0 * head_depth) + (2.0 * head_width) - (2.0 * neck_width)
+    }
+
+    /// Calculates the perimeter modification for a free-form edge.
+    /// The profile through the points replaces the straight span between its first and last points.
+    pub fn free_form_edge_modification(points: &[(f64, f64)], smoothed: bool) -> f64 {
+        let length = if smoothed { catmull_rom_length(points) } else { polyline_length(points) };
+        length - chord_length(points)
+    }
+
+    /// Calculates the straight distance between the first and last points.
+    pub fn chord_length(points: &[(f64, f64)]) -> f64 {
+        match (points.first(), points.last()) {
+            (Some(first), Some(last)) => { (last.0 - first.0).hypot(last.1 - first.1) }
+            _ => { 0.0 }
+        }
+    }
+
+    /// Calculates the length of straight lines through the points.
+    pub fn polyline_length(points: &[(f64, f64)]) -> f64 {
+        points.windows(2).map(|pair| (pair[1].0 - pair[0].0).hypot(pair[1].1 - pair[0].1)).sum()
+    }
+
+    /// Calculates the length of a Catmull-Rom curve through the points.
+    /// The end points are repeated so the curve starts and ends on them, and each span is measured as a fine polyline.
+    pub fn catmull_rom_length(points: &[(f64, f64)]) -> f64 {
+        polyline_length(&catmull_rom_points(points))
+    }
+
+    /// Returns points along a Catmull-Rom curve through the points, finely enough to stand in for the curve.
+    pub fn catmull_rom_points(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        const SAMPLES: usize = 64;
+        if points.len() < 3 { return points.to_vec(); }
+
+        let mut curve = Vec::new();
+        for span in 0..points.len() - 1 {
+            let p0 = points[span.saturating_sub(1)];
+            let p1 = points[span];
+            let p2 = points[span + 1];
+            let p3 = points[(span + 2).min(points.len() - 1)];
+            for sample in 0..SAMPLES {
+                let t = sample as f64 / SAMPLES as f64;
+                let blend = |a: f64, b: f64, c: f64, d: f64| {
+                    0.5 * ((2.0 * b) + ((c - a) * t) + (((2.0 * a) - (5.0 * b) + (4.0 * c) - d) * t * t) + (((3.0 * b) - a - (3.0 * c) + d) * t * t * t))
+                };
+                curve.push((blend(p0.0, p1.0, p2.0, p3.0), blend(p0.1, p1.1, p2.1, p3.1)));
+            }
+        }
+        curve.push(points[points.len() - 1]);
+        curve
+    }
+
+    /// Calculates the perimeter modification for a periodic edge.
+    /// Every repeat's profile length replaces one pitch of the straight edge.
+    pub fn periodic_edge_modification(profile: PeriodicProfile, edge_length: f64, pitch: f64, amplitude: f64) -> f64 {
+        let repeats = edge_length / pitch;
+        let repeat_length = match profile {
+            PeriodicProfile::Wave => { sine_wave_length(pitch, amplitude) }
+            PeriodicProfile::Serration => { 2.0 * (pitch / 2.0).hypot(2.0 * amplitude) }
+            PeriodicProfile::Scallops => {
+                // an arc across the pitch with a sagitta of twice the amplitude
+                let sagitta = 2.0 * amplitude;
+                if sagitta <= 0.0 { return 0.0; }
+                let radius = ((pitch * pitch / 4.0) + (sagitta * sagitta)) / (2.0 * sagitta);
+                2.0 * radius * (pitch / (2.0 * radius)).asin()
+            }
+        };
+        (repeat_length * repeats) - edge_length
+    }
+
+    /// Calculates the length of one wavelength of a sine wave, integrated numerically with Simpson's rule.
+    pub fn sine_wave_length(wavelength: f64, amplitude: f64) -> f64 {
+        const INTERVALS: usize = 256;
+        let slope = 2.0 * PI * amplitude / wavelength;
+        let integrand = |x: f64| (1.0 + (slope * (2.0 * PI * x / wavelength).cos()).powi(2)).sqrt();
+        let step = wavelength / INTERVALS as f64;
+        let mut sum = integrand(0.0) + integrand(wavelength);
+        for interval in 1..INTERVALS {
+            let weight = if interval % 2 == 0 { 2.0 } else { 4.0 };
+            sum += weight * integrand(interval as f64 * step);
+        }
+        sum * step / 3.0
+    }
+
 
 
     /// Calculates the perimeter modification for a circular arc.
@@ -1215,7 +4445,131 @@ pub mod formulas {
 
     /// Calculates the perimeter modification for an ellipse.
     pub fn ellipse_modification(width: f64, height: f64) -> f64 {
-        let ellipse = PI * (3.0 * ( (width/2.0) + height) - ( (((3.0*width)/2.0) + height) * ((width/2.0) + (3.0*height)) ).sqrt() );
+        let ellipse = ellipse_perimeter(width / 2.0, height);
         (ellipse / 2.0) - width
     }
+
+    /// Calculates the perimeter of a full ellipse from its semi-axes.
+    /// Uses the arithmetic-geometric mean form of the complete elliptic integral of the second kind:
+    /// P = 2π (a² - Σ 2ⁿ⁻¹ cₙ²) / AGM(a, b), which converges to machine precision in a few steps.
+    pub fn ellipse_perimeter(semi_width: f64, semi_height: f64) -> f64 {
+        let (mut a, mut b) = (semi_width.max(semi_height), semi_width.min(semi_height));
+        // a flat ellipse is a line traced there and back
+        if b <= 0.0 { return 4.0 * a; }
+        let mut sum = ((a * a) - (b * b)) / 2.0;
+        let mut weight = 1.0;
+        while (a - b).abs() > f64::EPSILON * a {
+            let c = (a - b) / 2.0;
+            (a, b) = ((a + b) / 2.0, (a * b).sqrt());
+            sum += weight * c * c;
+            weight *= 2.0;
+        }
+        2.0 * PI * ((semi_width.max(semi_height).powi(2)) - sum) / a
+    }
+
+    /// Converts an angle from an ellipse's center (in radians) to the ellipse's parametric angle.
+    pub fn ellipse_parameter(semi_width: f64, semi_height: f64, angle: f64) -> f64 {
+        let parameter = (semi_width * angle.sin()).atan2(semi_height * angle.cos());
+        // keeps the parameter on the same turn as the angle so spans past a half turn stay intact
+        parameter + (2.0 * PI * ((angle - parameter) / (2.0 * PI)).round())
+    }
+
+    /// Calculates the length of an ellipse between two parametric angles, integrated numerically with Simpson's rule.
+    pub fn ellipse_arc_length(semi_width: f64, semi_height: f64, start: f64, end: f64) -> f64 {
+        const INTERVALS: usize = 1024;
+        let integrand = |t: f64| (semi_width * t.sin()).hypot(semi_height * t.cos());
+        let step = (end - start) / INTERVALS as f64;
+        let mut sum = integrand(start) + integrand(end);
+        for interval in 1..INTERVALS {
+            let weight = if interval % 2 == 0 { 2.0 } else { 4.0 };
+            sum += weight * integrand(start + (interval as f64 * step));
+        }
+        (sum * step / 3.0).abs()
+    }
+
+    /// Calculates the length an elliptical arc spans along its chord and how far it reaches from the chord.
+    pub fn elliptical_arc_extent(width: f64, height: f64, start_angle: f64, end_angle: f64) -> (f64, f64) {
+        const SAMPLES: usize = 64;
+        let (semi_width, semi_height) = (width / 2.0, height / 2.0);
+        let start = ellipse_parameter(semi_width, semi_height, start_angle.to_radians());
+        let end = ellipse_parameter(semi_width, semi_height, end_angle.to_radians());
+        let (x0, y0) = (semi_width * start.cos(), semi_height * start.sin());
+        let (x1, y1) = (semi_width * end.cos(), semi_height * end.sin());
+        let chord = (x1 - x0).hypot(y1 - y0);
+        if chord <= 0.0 { return (width.max(height), width.max(height)); }
+        let reach = (0..=SAMPLES)
+            .map(|sample| {
+                let t = start + ((end - start) * sample as f64 / SAMPLES as f64);
+                let (x, y) = (semi_width * t.cos(), semi_height * t.sin());
+                (((x1 - x0) * (y - y0)) - ((y1 - y0) * (x - x0))).abs() / chord
+            })
+            .fold(0.0, f64::max);
+        (chord, reach)
+    }
+
+    /// Calculates the perimeter modification for a partial elliptical arc.
+    /// The arc between the start and end angles replaces the straight chord between its ends.
+    pub fn elliptical_arc_modification(width: f64, height: f64, start_angle: f64, end_angle: f64) -> f64 {
+        let (semi_width, semi_height) = (width / 2.0, height / 2.0);
+        let start = ellipse_parameter(semi_width, semi_height, start_angle.to_radians());
+        let end = ellipse_parameter(semi_width, semi_height, end_angle.to_radians());
+        let arc = ellipse_arc_length(semi_width, semi_height, start, end);
+        let chord = (semi_width * (end.cos() - start.cos())).hypot(semi_height * (end.sin() - start.sin()));
+        arc - chord
+    }
+
+    /// The radii that bound a gear's tooth outline.
+    pub struct GearRadii {
+        /// The base circle the involute flanks unwind from.
+        pub base: f64,
+        /// The radius of the tooth ends nearest the center.
+        pub inner: f64,
+        /// The radius of the tooth ends farthest from the center.
+        pub outer: f64,
+    }
+
+    /// Calculates the radii of a standard full depth gear (addendum m, dedendum 1.25m).
+    /// An external gear's tips are outside the pitch circle and an internal gear's tips are inside it.
+    pub fn gear_radii(module: f64, teeth: usize, pressure_angle: f64, internal: bool) -> GearRadii {
+        let pitch = module * teeth as f64 / 2.0;
+        let base = pitch * pressure_angle.to_radians().cos();
+        if internal {
+            GearRadii { base, inner: pitch - module, outer: pitch + (1.25 * module) }
+        }
+        else {
+            GearRadii { base, inner: pitch - (1.25 * module), outer: pitch + module }
+        }
+    }
+
+    /// Calculates the half angle of an external tooth (or an internal gear's tooth space) at a radius.
+    /// Below the base circle the flank runs straight toward the center.
+    pub fn gear_half_angle(module: f64, teeth: usize, pressure_angle: f64, radius: f64) -> f64 {
+        let involute = |angle: f64| angle.tan() - angle;
+        let radii = gear_radii(module, teeth, pressure_angle, false);
+        let profile_angle = (radii.base / radius.max(radii.base)).acos();
+        (PI / (2.0 * teeth as f64)) + involute(pressure_angle.to_radians()) - involute(profile_angle)
+    }
+
+    /// Calculates the length of a gear's full tooth outline.
+    /// Each tooth has two flanks, measured numerically along the involute, joined by lands on the inner and outer circles.
+    pub fn gear_outline_length(module: f64, teeth: usize, pressure_angle: f64, internal: bool) -> f64 {
+        const SAMPLES: usize = 200;
+        let radii = gear_radii(module, teeth, pressure_angle, internal);
+        let half_angle = |radius: f64| gear_half_angle(module, teeth, pressure_angle, radius);
+
+        // the straight flank below the base circle and the involute above it
+        let involute_start = radii.base.max(radii.inner);
+        let mut flank = involute_start - radii.inner;
+        for sample in 0..SAMPLES {
+            let r0 = involute_start + ((radii.outer - involute_start) * sample as f64 / SAMPLES as f64);
+            let r1 = involute_start + ((radii.outer - involute_start) * (sample + 1) as f64 / SAMPLES as f64);
+            let (x0, y0) = (r0 * half_angle(r0).cos(), r0 * half_angle(r0).sin());
+            let (x1, y1) = (r1 * half_angle(r1).cos(), r1 * half_angle(r1).sin());
+            flank += (x1 - x0).hypot(y1 - y0);
+        }
+
+        let outer_land = radii.outer * 2.0 * half_angle(radii.outer);
+        let inner_land = radii.inner * ((2.0 * PI / teeth as f64) - (2.0 * half_angle(involute_start)));
+        ((2.0 * flank) + outer_land + inner_land) * teeth as f64
+    }
 }
\ No newline at end of file
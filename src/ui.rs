@@ -4,12 +4,13 @@ use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 use crate::app::{App, Pages};
+use crate::shapes::BodyShape;
 
 pub mod standard {
     use ratatui::prelude::*;
-    use ratatui::layout::*;
 
     /// Creates a centered rect using up a certain percentage of the available rect.
+    #[allow(dead_code)]
     fn centered_rect(percent_x: u16, percent_y: u16, rect: Rect) -> Rect {
         // Cut the given rectangle into three vertical pieces
         let vertical_chunks = Layout::new(Direction::Vertical, [
@@ -39,6 +40,7 @@ pub fn ui(frame: &mut Frame, app: &App) {
         Line::raw("PERI"),
         Line::raw(app.project.as_str()),
         Line::raw(app.current_page_name()),
+        Line::raw(app.message.as_str()),
     ]).block(header_block);
 
     // footer
@@ -49,7 +51,7 @@ pub fn ui(frame: &mut Frame, app: &App) {
 
     // The sections of the screen.
     let leaflets = Layout::new(Direction::Vertical, [
-        Constraint::Length(5), // header
+        Constraint::Length(6), // header
         Constraint::Fill(1), // body
         Constraint::Length(footer_height), // footer
     ]).split(frame.area());
@@ -73,11 +75,17 @@ pub fn ui(frame: &mut Frame, app: &App) {
         }
 
         Pages::AddingBody => {
-            let body = Paragraph::new(vec![
-                Line::raw(format!("Name  : {}", &app.new_body_name)),
-                Line::raw(format!("Width : {}", &app.new_body_width)),
-                Line::raw(format!("Height: {}", &app.new_body_height)),
-            ]);
+            let mut lines = vec![
+                Line::raw(format!("Name : {}", &app.new_body_name)),
+                Line::raw(format!("Shape: {}", if app.is_shape_set { app.new_body_shape.name() } else { "".to_string() })),
+            ];
+            if app.is_shape_set {
+                let values = [&app.new_body_width, &app.new_body_height];
+                for (name, value) in app.new_body_shape.dimension_names().iter().zip(values) {
+                    lines.push(Line::raw(format!("{}: {}", name, value)));
+                }
+            }
+            let body = Paragraph::new(lines);
 
             frame.render_widget(body, leaflets[1]);
         }
@@ -93,6 +101,16 @@ pub fn ui(frame: &mut Frame, app: &App) {
             frame.render_widget(body, leaflets[1]);
         }
 
+        Pages::SettingQuantity => {
+            let body = Paragraph::new(format!("New Quantity: {}", &app.new_body_quantity));
+            frame.render_widget(body, leaflets[1]);
+        }
+
+        Pages::SettingKerf => {
+            let body = Paragraph::new(format!("New Kerf: {}", &app.new_body_kerf));
+            frame.render_widget(body, leaflets[1]);
+        }
+
         Pages::ShowingHoleFeatureOptions => {
             let body = Paragraph::new("Select hole type...");
             frame.render_widget(body, leaflets[1]);
@@ -113,13 +131,48 @@ pub fn ui(frame: &mut Frame, app: &App) {
             frame.render_widget(body, leaflets[1]);
         }
 
+        Pages::ShowingExportOptions => {
+            let body = Paragraph::new(vec![
+                Line::raw("Select export format..."),
+                Line::raw(format!("Pdf font: {}", app.pdf_font.name())),
+                Line::raw(format!("G-code units: {}", app.gcode_units.name())),
+                Line::raw(format!("Finished bodies in project: {}", app.bodies.len())),
+            ]);
+            frame.render_widget(body, leaflets[1]);
+        }
+
+        Pages::Verifying => {
+            let body_text = app.current_feature_page();
+            let body = Paragraph::new(body_text.iter().map(|line| Line::raw(line.as_str())).collect::<Vec<_>>());
+            frame.render_widget(body, leaflets[1]);
+        }
+
+        Pages::ImportingBody => {
+            let mut lines = vec![Line::raw(format!("Csv Path: {}", &app.import_path))];
+            if !app.import_errors.is_empty() {
+                lines.push(Line::raw(""));
+                lines.push(Line::raw("Could not import the body:"));
+                lines.extend(app.import_errors.iter().map(|error| Line::raw(error.as_str())));
+            }
+            let body = Paragraph::new(lines);
+            frame.render_widget(body, leaflets[1]);
+        }
+
         Pages::AddingFeature => {
             let body;
             if let Some(path) = &app.current_feature_addition_path {
-                body = Paragraph::new(vec![
+                let mut lines = vec![
                     Line::from(format!("Adding {}", path.feature.name())),
                     Line::from(format!("{}: {}", path.current_step_value(), path.current_step_value_input())),
-                ]);
+                ];
+                if let (Some(kind), "placement") = (path.feature.placement_kind(), path.current_step_value().as_str()) {
+                    lines.push(Line::from(format!("optional: {} (leave blank to skip)", kind.hint())));
+                }
+                if let Some(error) = &path.error {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(format!("Invalid {}: {}", path.feature.name(), error)));
+                }
+                body = Paragraph::new(lines);
             }
             else {
                 body = Paragraph::new("Cannot display feature type");
@@ -139,7 +192,10 @@ pub fn ui(frame: &mut Frame, app: &App) {
         }
 
         Pages::FinishingBody => {
-            let body = Paragraph::new("Complete body?");
+            let body = Paragraph::new(vec![
+                Line::raw("Complete body?"),
+                Line::raw(format!("Pdf font: {}", app.pdf_font.name())),
+            ]);
             frame.render_widget(body, leaflets[1]);
         }
 
@@ -184,12 +240,12 @@ impl Instruction {
 
             // adds the current instruction to the current line
             amount_in_group += 1;
-            if current_group != "" { current_group += " | "; }
+            if !current_group.is_empty() { current_group += " | "; }
             current_group += instruction.printed().as_str();
         }
 
         // adds the last line to the list of lines if it isn't empty
-        if current_group != "" { lines.push(Line::from(current_group)); }
+        if !current_group.is_empty() { lines.push(Line::from(current_group)); }
 
         // returns the list of lines
         lines
@@ -205,18 +261,56 @@ impl Instruction {
     //      body/feature management
     pub fn rename_project_instruction() -> Instruction { Instruction::new("P".to_string(), "rename project".to_string(), KeyCode::Char('p')) }
     pub fn rename_instruction() -> Instruction { Instruction::new("N".to_string(), "rename body".to_string(), KeyCode::Char('n')) }
+    pub fn set_quantity_instruction() -> Instruction { Instruction::new("U".to_string(), "set quantity".to_string(), KeyCode::Char('u')) }
+    pub fn set_kerf_instruction() -> Instruction { Instruction::new("K".to_string(), "set kerf".to_string(), KeyCode::Char('k')) }
     pub fn finish_instruction() -> Instruction { Instruction::new("F".to_string(), "finish".to_string(), KeyCode::Char('f')) }
+    pub fn escape_cancel_instruction() -> Instruction { Instruction::new("ESC".to_string(), "cancel".to_string(), KeyCode::Esc) }
+    pub fn finish_with_report_instruction() -> Instruction { Instruction::new("5".to_string(), "finish and export project report".to_string(), KeyCode::Char('5')) }
+    pub fn change_gcode_units_instruction() -> Instruction { Instruction::new("G".to_string(), "change g-code units".to_string(), KeyCode::Char('g')) }
+    pub fn change_font_instruction() -> Instruction { Instruction::new("T".to_string(), "change font".to_string(), KeyCode::Char('t')) }
+    pub fn body_shape_instructions() -> Vec<Instruction> {
+        BodyShape::all()
+            .iter()
+            .enumerate()
+            .map(|(index, shape)| {
+                let key = char::from_digit(index as u32 + 1, 10).unwrap_or('0');
+                Instruction::new(key.to_string(), shape.name(), KeyCode::Char(key))
+            })
+            .collect()
+    }
     pub fn reset_instruction() -> Instruction { Instruction::new("ESC".to_string(), "reset".to_string(), KeyCode::Esc) }
     pub fn remove_feature_instruction() -> Instruction { Instruction::new("R".to_string(), "remove feature".to_string(), KeyCode::Char('r')) }
+    //      import/export
+    pub fn export_instruction() -> Instruction { Instruction::new("E".to_string(), "export".to_string(), KeyCode::Char('e')) }
+    pub fn import_instruction() -> Instruction { Instruction::new("I".to_string(), "import body".to_string(), KeyCode::Char('i')) }
+    pub fn export_pdf_instruction() -> Instruction { Instruction::new("1".to_string(), "export pdf".to_string(), KeyCode::Char('1')) }
+    pub fn export_csv_instruction() -> Instruction { Instruction::new("2".to_string(), "export csv".to_string(), KeyCode::Char('2')) }
+    pub fn export_markdown_instruction() -> Instruction { Instruction::new("3".to_string(), "export markdown".to_string(), KeyCode::Char('3')) }
+    pub fn export_html_instruction() -> Instruction { Instruction::new("4".to_string(), "export html".to_string(), KeyCode::Char('4')) }
+    pub fn export_text_instruction() -> Instruction { Instruction::new("6".to_string(), "export text".to_string(), KeyCode::Char('6')) }
+    pub fn export_dxf_instruction() -> Instruction { Instruction::new("7".to_string(), "export dxf".to_string(), KeyCode::Char('7')) }
+    pub fn export_svg_instruction() -> Instruction { Instruction::new("8".to_string(), "export svg".to_string(), KeyCode::Char('8')) }
+    pub fn export_gcode_instruction() -> Instruction { Instruction::new("9".to_string(), "export g-code".to_string(), KeyCode::Char('9')) }
+    pub fn verify_instruction() -> Instruction { Instruction::new("V".to_string(), "verify".to_string(), KeyCode::Char('v')) }
+    pub fn copy_instruction() -> Instruction { Instruction::new("C".to_string(), "copy summary".to_string(), KeyCode::Char('c')) }
+    pub fn export_project_report_instruction() -> Instruction { Instruction::new("5".to_string(), "export project report".to_string(), KeyCode::Char('5')) }
     //      holes
     pub fn add_hole_instruction() -> Instruction { Instruction::new("0".to_string(), "add hole".to_string(), KeyCode::Char('0')) }
     pub fn add_circular_hole_instruction() -> Instruction { Instruction::new("1".to_string(), "add circular hole".to_string(), KeyCode::Char('1')) }
     pub fn add_capsular_hole_instruction() -> Instruction { Instruction::new("2".to_string(), "add capsular hole".to_string(), KeyCode::Char('2')) }
     pub fn add_rectangular_hole_instruction() -> Instruction { Instruction::new("3".to_string(), "add rectangular hole".to_string(), KeyCode::Char('3')) }
+    pub fn add_polygon_hole_instruction() -> Instruction { Instruction::new("4".to_string(), "add polygon hole".to_string(), KeyCode::Char('4')) }
+    pub fn add_keyhole_instruction() -> Instruction { Instruction::new("5".to_string(), "add keyhole".to_string(), KeyCode::Char('5')) }
+    pub fn add_d_hole_instruction() -> Instruction { Instruction::new("6".to_string(), "add d hole".to_string(), KeyCode::Char('6')) }
+    pub fn add_polar_hole_pattern_instruction() -> Instruction { Instruction::new("7".to_string(), "add polar hole pattern".to_string(), KeyCode::Char('7')) }
+    pub fn add_grid_hole_pattern_instruction() -> Instruction { Instruction::new("8".to_string(), "add grid hole pattern".to_string(), KeyCode::Char('8')) }
+    pub fn add_elliptical_hole_instruction() -> Instruction { Instruction::new("9".to_string(), "add elliptical hole".to_string(), KeyCode::Char('9')) }
+    pub fn add_segment_hole_instruction() -> Instruction { Instruction::new("0".to_string(), "add segment hole".to_string(), KeyCode::Char('0')) }
     //      corners
     pub fn add_corner_instruction() -> Instruction { Instruction::new("1".to_string(), "add corner".to_string(), KeyCode::Char('1')) }
     pub fn add_fillet_instruction() -> Instruction { Instruction::new("1".to_string(), "add fillet".to_string(), KeyCode::Char('1')) }
     pub fn add_chamfer_instruction() -> Instruction { Instruction::new("2".to_string(), "add chamfer".to_string(), KeyCode::Char('2')) }
+    pub fn add_internal_corner_instruction() -> Instruction { Instruction::new("3".to_string(), "add internal corner".to_string(), KeyCode::Char('3')) }
     pub fn add_slope_instruction() -> Instruction { Instruction::new("4".to_string(), "add slope".to_string(), KeyCode::Char('4')) }
     pub fn add_cliff_instruction() -> Instruction { Instruction::new("5".to_string(), "add cliff".to_string(), KeyCode::Char('5')) }
     //      cutout
@@ -225,10 +319,17 @@ impl Instruction {
     pub fn add_sawtooth_instruction() -> Instruction { Instruction::new("2".to_string(), "add sawtooth".to_string(), KeyCode::Char('2')) }
     pub fn add_claw_instruction() -> Instruction { Instruction::new("3".to_string(), "add claw".to_string(), KeyCode::Char('3')) }
     pub fn add_composite_slope_instruction() -> Instruction { Instruction::new("4".to_string(), "add composite slope".to_string(), KeyCode::Char('4')) }
+    pub fn add_edge_slot_instruction() -> Instruction { Instruction::new("5".to_string(), "add edge slot".to_string(), KeyCode::Char('5')) }
+    pub fn add_dovetail_instruction() -> Instruction { Instruction::new("6".to_string(), "add dovetail".to_string(), KeyCode::Char('6')) }
+    pub fn add_t_slot_instruction() -> Instruction { Instruction::new("7".to_string(), "add t-slot".to_string(), KeyCode::Char('7')) }
+    pub fn add_free_form_edge_instruction() -> Instruction { Instruction::new("8".to_string(), "add free-form edge".to_string(), KeyCode::Char('8')) }
+    pub fn add_periodic_edge_instruction() -> Instruction { Instruction::new("9".to_string(), "add periodic edge".to_string(), KeyCode::Char('9')) }
     //      circular
     pub fn add_circular_feature_instruction() -> Instruction { Instruction::new("3".to_string(), "add circular feature".to_string(), KeyCode::Char('3')) }
     pub fn add_arc_instruction() -> Instruction { Instruction::new("1".to_string(), "add arc".to_string(), KeyCode::Char('1')) }
     pub fn add_ellipse_instruction() -> Instruction { Instruction::new("2".to_string(), "add ellipse".to_string(), KeyCode::Char('2')) }
+    pub fn add_gear_instruction() -> Instruction { Instruction::new("3".to_string(), "add gear".to_string(), KeyCode::Char('3')) }
+    pub fn add_elliptical_arc_instruction() -> Instruction { Instruction::new("4".to_string(), "add elliptical arc".to_string(), KeyCode::Char('4')) }
 
     //      other
     pub fn add_other_feature_instruction() -> Instruction { Instruction::new("4".to_string(), "add other feature".to_string(), KeyCode::Char('4')) }
@@ -236,8 +337,8 @@ impl Instruction {
 
 
 
-pub fn get_instructions_for(page: &Pages) -> Vec<Line> {
-    return match page {
+pub fn get_instructions_for(page: &Pages) -> Vec<Line<'static>> {
+    match page {
         Pages::Launching => {
             Instruction::in_groups(vec![], 4)
         }
@@ -250,10 +351,10 @@ pub fn get_instructions_for(page: &Pages) -> Vec<Line> {
         }
 
         Pages::AddingBody => {
-            Instruction::in_groups(vec![
-                Instruction::confirm_instruction(),
-                Instruction::reset_instruction(),
-            ], 4)
+            let mut instructions = Instruction::body_shape_instructions();
+            instructions.push(Instruction::confirm_instruction());
+            instructions.push(Instruction::reset_instruction());
+            Instruction::in_groups(instructions, 4)
         }
 
         Pages::BodyView => {
@@ -267,7 +368,13 @@ pub fn get_instructions_for(page: &Pages) -> Vec<Line> {
                 Instruction::add_other_feature_instruction(),
                 Instruction::rename_project_instruction(),
                 Instruction::rename_instruction(),
+                Instruction::set_quantity_instruction(),
+                Instruction::set_kerf_instruction(),
                 Instruction::remove_feature_instruction(),
+                Instruction::export_instruction(),
+                Instruction::import_instruction(),
+                Instruction::copy_instruction(),
+                Instruction::verify_instruction(),
                 Instruction::finish_instruction(),
                 Instruction::reset_instruction(),
                 Instruction::quit_instruction(),
@@ -281,11 +388,32 @@ pub fn get_instructions_for(page: &Pages) -> Vec<Line> {
             ], 4)
         }
 
+        Pages::SettingQuantity => {
+            Instruction::in_groups(vec![
+                Instruction::confirm_instruction(),
+                Instruction::reset_instruction(),
+            ], 4)
+        }
+
+        Pages::SettingKerf => {
+            Instruction::in_groups(vec![
+                Instruction::confirm_instruction(),
+                Instruction::reset_instruction(),
+            ], 4)
+        }
+
         Pages::ShowingHoleFeatureOptions => {
             Instruction::in_groups(vec![
                 Instruction::add_circular_hole_instruction(),
                 Instruction::add_capsular_hole_instruction(),
                 Instruction::add_rectangular_hole_instruction(),
+                Instruction::add_polygon_hole_instruction(),
+                Instruction::add_keyhole_instruction(),
+                Instruction::add_d_hole_instruction(),
+                Instruction::add_polar_hole_pattern_instruction(),
+                Instruction::add_grid_hole_pattern_instruction(),
+                Instruction::add_elliptical_hole_instruction(),
+                Instruction::add_segment_hole_instruction(),
                 Instruction::cancel_instruction(),
             ], 4)
         }
@@ -294,6 +422,7 @@ pub fn get_instructions_for(page: &Pages) -> Vec<Line> {
             Instruction::in_groups(vec![
                 Instruction::add_fillet_instruction(),
                 Instruction::add_chamfer_instruction(),
+                Instruction::add_internal_corner_instruction(),
                 Instruction::add_slope_instruction(),
                 Instruction::add_cliff_instruction(),
                 Instruction::cancel_instruction(),
@@ -306,6 +435,11 @@ pub fn get_instructions_for(page: &Pages) -> Vec<Line> {
                 Instruction::add_sawtooth_instruction(),
                 Instruction::add_claw_instruction(),
                 Instruction::add_composite_slope_instruction(),
+                Instruction::add_edge_slot_instruction(),
+                Instruction::add_dovetail_instruction(),
+                Instruction::add_t_slot_instruction(),
+                Instruction::add_free_form_edge_instruction(),
+                Instruction::add_periodic_edge_instruction(),
                 Instruction::cancel_instruction(),
             ], 4)
         }
@@ -314,10 +448,44 @@ pub fn get_instructions_for(page: &Pages) -> Vec<Line> {
             Instruction::in_groups(vec![
                 Instruction::add_arc_instruction(),
                 Instruction::add_ellipse_instruction(),
+                Instruction::add_gear_instruction(),
+                Instruction::add_elliptical_arc_instruction(),
                 Instruction::cancel_instruction(),
             ], 4)
         }
 
+        Pages::ShowingExportOptions => {
+            Instruction::in_groups(vec![
+                Instruction::export_pdf_instruction(),
+                Instruction::export_csv_instruction(),
+                Instruction::export_markdown_instruction(),
+                Instruction::export_html_instruction(),
+                Instruction::export_project_report_instruction(),
+                Instruction::export_text_instruction(),
+                Instruction::export_dxf_instruction(),
+                Instruction::export_svg_instruction(),
+                Instruction::export_gcode_instruction(),
+                Instruction::change_font_instruction(),
+                Instruction::change_gcode_units_instruction(),
+                Instruction::cancel_instruction(),
+            ], 4)
+        }
+
+        Pages::Verifying => {
+            Instruction::in_groups(vec![
+                Instruction::previous_page(),
+                Instruction::next_page(),
+                Instruction::cancel_instruction(),
+            ], 4)
+        }
+
+        Pages::ImportingBody => {
+            Instruction::in_groups(vec![
+                Instruction::confirm_instruction(),
+                Instruction::escape_cancel_instruction(),
+            ], 4)
+        }
+
         Pages::AddingFeature => {
             Instruction::in_groups(vec![
                 Instruction::confirm_instruction(),
@@ -341,6 +509,8 @@ pub fn get_instructions_for(page: &Pages) -> Vec<Line> {
         Pages::FinishingBody => {
             Instruction::in_groups(vec![
                 Instruction::confirm_instruction(),
+                Instruction::finish_with_report_instruction(),
+                Instruction::change_font_instruction(),
                 Instruction::cancel_instruction(),
             ], 4)
         }